@@ -1,2 +1,3 @@
 pub mod stated;
+pub mod stated_builder;
 pub mod stated_internal;
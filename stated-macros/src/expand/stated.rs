@@ -1,10 +1,13 @@
 use proc_macro2::TokenStream as TokenStream2;
 use quote::format_ident;
-use syn::{ItemImpl, ItemStruct, Meta, Result, Token, punctuated::Punctuated};
+use syn::{ItemEnum, ItemImpl, ItemStruct, Meta, Result, Token, punctuated::Punctuated};
 
 use crate::{
     extensions::ty::{TypeExt, TypePathExt},
-    utilities::squote::squote,
+    utilities::{
+        field::FieldAttrs,
+        squote::{parse_squote, squote},
+    },
 };
 
 pub fn expand_item_struct(
@@ -13,16 +16,38 @@ pub fn expand_item_struct(
 ) -> Result<TokenStream2> {
     let macro_name = format_ident!("__{}", item_struct.ident);
 
+    // The impl blocks must bound the states that field types depend on, so those
+    // states are exported alongside the metas.
+    let mut dependent_states = Vec::new();
+
+    for field in item_struct.fields.iter() {
+        if let Some(dependent_ty) = FieldAttrs::from_field(field)?.dependent_ty {
+            let state = dependent_ty.state;
+
+            dependent_states.push(match state.group {
+                Some(group) => squote!(#group::#{state.ident}),
+                None => squote!(#{state.ident}),
+            });
+        }
+    }
+
+    let mut exported_metas = metas.clone();
+
+    if !dependent_states.is_empty() {
+        exported_metas.push(parse_squote!(__dependent(#(#dependent_states),*)));
+    }
+
     Ok(squote! {
-        // Re-emit the struct with the internal macro.
-        #[::stated::stated_internal(#metas)]
+        // Re-emit the struct with the internal macro. The exported metas are attached,
+        // since the struct expands impl blocks for its accessors.
+        #[::stated::stated_internal(#exported_metas)]
         #item_struct
 
         #[doc(hidden)]
         macro_rules! #macro_name {
             ($($tt:tt)*) => {
-                // Re-emit the input, but with the metas attached.
-                #[::stated::stated_internal(#metas)]
+                // Re-emit the input, but with the exported metas attached.
+                #[::stated::stated_internal(#exported_metas)]
                 $($tt)*
             }
         }
@@ -32,13 +57,52 @@ pub fn expand_item_struct(
     })
 }
 
-pub fn expand_item_impl(item_impl: ItemImpl) -> Result<TokenStream2> {
+pub fn expand_item_enum(
+    metas: Punctuated<Meta, Token![,]>,
+    item_enum: ItemEnum,
+) -> Result<TokenStream2> {
+    let macro_name = format_ident!("__{}", item_enum.ident);
+
+    // The impl block constructs variants rather than the enum itself, so the
+    // variants are exported alongside the metas.
+    let variants = item_enum.variants.iter().map(|variant| &variant.ident);
+    let mut exported_metas = metas.clone();
+    exported_metas.push(parse_squote!(__variants(#(#variants),*)));
+
+    Ok(squote! {
+        // Re-emit the enum with the internal macro.
+        #[::stated::stated_internal(#metas)]
+        #item_enum
+
+        #[doc(hidden)]
+        macro_rules! #macro_name {
+            ($($tt:tt)*) => {
+                // Re-emit the input, but with the exported metas attached.
+                #[::stated::stated_internal(#exported_metas)]
+                $($tt)*
+            }
+        }
+
+        // Make the macro public with the same name as the enum.
+        pub(crate) use #macro_name as #{item_enum.ident};
+    })
+}
+
+pub fn expand_item_impl(
+    metas: Punctuated<Meta, Token![,]>,
+    mut item_impl: ItemImpl,
+) -> Result<TokenStream2> {
     // Expect a macro at the impl type path with the same name.
     let mut macro_path = item_impl.self_ty.require_path()?.clone();
 
     // Strip the generic arguments from the macro path.
     macro_path.strip_generics();
 
+    // Re-attach the metas as the ruleset of the impl block.
+    if !metas.is_empty() {
+        item_impl.attrs.push(parse_squote!(#[stated(#metas)]));
+    }
+
     Ok(squote! {
         #macro_path!(#item_impl);
     })
@@ -0,0 +1,325 @@
+use itertools::Itertools;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::format_ident;
+use syn::{
+    Attribute, Data, DataStruct, DeriveInput, Error, Expr, Fields, FieldsNamed, GenericParam,
+    Ident, Result, Token, ext::IdentExt,
+};
+
+use crate::utilities::squote::{parse_squote, squote};
+
+pub fn expand_derive_stated_builder(input: DeriveInput) -> Result<TokenStream2> {
+    let Data::Struct(DataStruct {
+        fields: Fields::Named(fields),
+        ..
+    }) = &input.data
+    else {
+        return Err(Error::new(
+            input.ident.span(),
+            "only structs with named fields can derive a builder",
+        ));
+    };
+
+    let defaults = fields
+        .named
+        .iter()
+        .map(|field| Ok(BuilderAttrs::from_attrs(&field.attrs)?.default))
+        .collect::<Result<Vec<_>>>()?;
+
+    // Without a required field there is no state to track.
+    if defaults.iter().all(Option::is_some) {
+        return Ok(expand_stateless_builder(
+            &input,
+            fields,
+            defaults.into_iter().flatten(),
+        ));
+    }
+
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let builder_ident = format_ident!("{ident}Builder");
+
+    // The designated parameter is added after the struct's own parameters, and
+    // defaults to a new builder.
+    let mut builder_generics = input.generics.clone();
+    builder_generics.params.push(parse_squote!(
+        #[stated]
+        __S = Initial
+    ));
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (builder_impl_generics, builder_ty_generics, _) = builder_generics.split_for_impl();
+
+    let builder_args = input
+        .generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Type(ty_param) => squote!(#{ty_param.ident}),
+            GenericParam::Lifetime(lifetime_param) => squote!(#{lifetime_param.lifetime}),
+            GenericParam::Const(const_param) => squote!(#{const_param.ident}),
+        })
+        .collect_vec();
+
+    let builder_ty = squote!(#builder_ident<#(#builder_args,)* _>);
+
+    let mut builder_fields = Vec::new();
+    let mut initializers = Vec::new();
+    let mut setters = Vec::new();
+    let mut required_states = Vec::new();
+
+    let members = fields
+        .named
+        .iter()
+        .map(|field| field.ident.as_ref().expect("fields are named"))
+        .collect_vec();
+
+    for (field, default) in fields.named.iter().zip(defaults) {
+        let field_ident = field.ident.as_ref().expect("fields are named");
+        let ty = &field.ty;
+
+        let doc = format!(" Set `{}`.", field_ident.unraw());
+
+        let Some(default) = default else {
+            // Required fields are only stored once they're set, and can only be set once.
+            let state = format_ident!("Has{}", upper_camel_case(&field_ident.unraw()));
+
+            builder_fields.push(squote! {
+                #[stated(when(#state) = #ty)]
+                #field_ident: ()
+            });
+            initializers.push(squote!(#field_ident: ()));
+
+            // The other fields are moved, since the struct is reconstructed with the field
+            // set.
+            let other_members = members.iter().filter(|member| *member != &field_ident);
+
+            setters.push(squote! {
+                #[doc = #doc]
+                #[stated(reject(#state), assign(#state))]
+                #vis fn #field_ident(self, #field_ident: #ty) -> #builder_ty {
+                    #builder_ident {
+                        #field_ident,
+                        #(#other_members: self.#other_members,)*
+                    }
+                }
+            });
+
+            required_states.push(state);
+            continue;
+        };
+
+        builder_fields.push(squote!(#field_ident: #ty));
+        initializers.push(squote!(#field_ident: #default));
+
+        setters.push(squote! {
+            #[doc = #doc]
+            #[stated]
+            #vis fn #field_ident(mut self, #field_ident: #ty) -> #builder_ty {
+                self.#field_ident = #field_ident;
+                _
+            }
+        });
+    }
+
+    let struct_doc = format!(" A builder for [`{ident}`].");
+    let new_doc = format!(" Create a new builder for [`{ident}`].");
+    let build_doc = format!(" Build the [`{ident}`].");
+
+    // The builder starts with every state disabled.
+    let disabled = required_states.iter().map(|_| squote!(::stated::N));
+    let new_builder_ty = squote!(#builder_ident<#(#builder_args,)* (#(#disabled),*)>);
+
+    let builder_struct = squote! {
+        #[doc = #struct_doc]
+        #[::stated::stated(states(#(#required_states),*))]
+        #vis struct #builder_ident #builder_generics #where_clause {
+            #(#builder_fields,)*
+        }
+    };
+
+    let new_fn = squote! {
+        #[doc = #new_doc]
+        #[stated]
+        #vis fn new() -> #builder_ty {
+            #builder_ident {
+                #(#initializers,)*
+            }
+        }
+    };
+
+    let build_fn = squote! {
+        #[doc = #build_doc]
+        #[stated(assert(#(#required_states),*))]
+        #vis fn build(self) -> #ident #ty_generics {
+            #ident {
+                #(#members: self.#members,)*
+            }
+        }
+    };
+
+    let builder_impl = squote! {
+        #[::stated::stated]
+        impl #builder_impl_generics #builder_ident #builder_ty_generics #where_clause {
+            #new_fn
+
+            #(#setters)*
+
+            #build_fn
+        }
+    };
+
+    Ok(squote! {
+        #builder_struct
+
+        #builder_impl
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Create a new builder.
+            #vis fn builder() -> #new_builder_ty {
+                #builder_ident::new()
+            }
+        }
+    })
+}
+
+/// Generate a plain builder for a struct whose `fields` are all optional, and
+/// start with `defaults`.
+fn expand_stateless_builder(
+    input: &DeriveInput,
+    fields: &FieldsNamed,
+    defaults: impl Iterator<Item = Expr>,
+) -> TokenStream2 {
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let builder_ident = format_ident!("{ident}Builder");
+    let generics = &input.generics;
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let members = fields
+        .named
+        .iter()
+        .map(|field| field.ident.as_ref().expect("fields are named"))
+        .collect_vec();
+
+    let builder_fields = fields
+        .named
+        .iter()
+        .map(|field| squote!(#{field.ident}: #{field.ty}));
+
+    let setters = fields.named.iter().map(|field| {
+        let field_ident = field.ident.as_ref().expect("fields are named");
+        let doc = format!(" Set `{}`.", field_ident.unraw());
+
+        squote! {
+            #[doc = #doc]
+            #vis fn #field_ident(mut self, #field_ident: #{field.ty}) -> Self {
+                self.#field_ident = #field_ident;
+                self
+            }
+        }
+    });
+
+    let struct_doc = format!(" A builder for [`{ident}`].");
+    let new_doc = format!(" Create a new builder for [`{ident}`].");
+    let build_doc = format!(" Build the [`{ident}`].");
+
+    let builder_struct = squote! {
+        #[doc = #struct_doc]
+        #vis struct #builder_ident #generics #where_clause {
+            #(#builder_fields,)*
+        }
+    };
+
+    let new_fn = squote! {
+        #[doc = #new_doc]
+        #vis fn new() -> Self {
+            #builder_ident {
+                #(#members: #defaults,)*
+            }
+        }
+    };
+
+    let build_fn = squote! {
+        #[doc = #build_doc]
+        #vis fn build(self) -> #ident #ty_generics {
+            #ident {
+                #(#members: self.#members,)*
+            }
+        }
+    };
+
+    squote! {
+        #builder_struct
+
+        impl #impl_generics #builder_ident #ty_generics #where_clause {
+            #new_fn
+
+            #(#setters)*
+
+            #build_fn
+        }
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Create a new builder.
+            #vis fn builder() -> #builder_ident #ty_generics {
+                #builder_ident::new()
+            }
+        }
+    }
+}
+
+/// The builder attributes of a field.
+#[derive(Default)]
+struct BuilderAttrs {
+    /// The initial value of the field, if it's optional.
+    default: Option<Expr>,
+}
+
+impl BuilderAttrs {
+    /// Parse the builder attributes from `attrs`.
+    fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut builder_attrs = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("builder")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("default") {
+                    if builder_attrs.default.is_some() {
+                        return Err(meta.error("redundant `default` attribute"));
+                    }
+
+                    // Without a value, the field starts with its default value.
+                    builder_attrs.default =
+                        Some(if meta.input.is_empty() || meta.input.peek(Token![,]) {
+                            parse_squote!(::std::default::Default::default())
+                        } else {
+                            meta.value()?.parse()?
+                        });
+                } else {
+                    return Err(meta.error("invalid attribute"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(builder_attrs)
+    }
+}
+
+/// Convert `ident`, which is in snake case, to upper camel case.
+fn upper_camel_case(ident: &Ident) -> String {
+    ident
+        .to_string()
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
@@ -1,10 +1,13 @@
 use std::mem;
 
 use itertools::Itertools;
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
+use quote::format_ident;
 use syn::{
-    Error, Fields, FieldsNamed, FieldsUnnamed, ImplItem, ItemImpl, ItemStruct, Meta, MetaList,
-    Result, Token, Type, parse::Parser, punctuated::Punctuated, spanned::Spanned,
+    Attribute, Block, Error, Fields, FieldsNamed, FieldsUnnamed, GenericArgument, GenericParam,
+    Generics, Ident, ImplItem, ImplItemFn, ItemEnum, ItemImpl, ItemStruct, ItemType, Meta,
+    MetaList, Path, PredicateType, Result, Token, Type, TypePath, TypeReference, Visibility,
+    WherePredicate, ext::IdentExt, parse::Parser, punctuated::Punctuated, spanned::Spanned,
     visit_mut::VisitMut,
 };
 
@@ -17,11 +20,16 @@ use crate::{
         ty::{TypeExt, TypePathExt},
     },
     utilities::{
-        designated::{find_designated_arg, get_designated_indices},
+        designated::{Designated, find_designated_arg, get_designated_params},
         documentation::{Description, DescriptionLine, Documentation},
+        field::FieldAttrs,
         squote::{parse_squote, squote},
-        stateset::Stateset,
-        visit::{AddFieldInStructConstruction, ReplaceExprInfer, ReplaceTypeInfer},
+        stateset::{Comparison, Conditional, State, StateExpr, Stateset},
+        visit::{
+            AddFieldInStructConstruction, AddFieldInStructPattern, FindExprInfer, FindTypeParam,
+            ReplaceExprInfer, ReplaceSelfInStructConstruction, ReplaceSelfTy, ReplaceTypeInfer,
+            ReplaceTypeInferInArgs, ReplaceTypeParam,
+        },
     },
 };
 
@@ -34,7 +42,13 @@ pub fn expand_item_struct_internal(
         .iter()
         .filter(|meta| !meta.path().is_ident("states"))
         .filter(|meta| !meta.path().is_ident("preset"))
-        .find(|meta| !meta.path().is_ident("docs"))
+        .filter(|meta| !meta.path().is_ident("group"))
+        .filter(|meta| !meta.path().is_ident("counter"))
+        .filter(|meta| !meta.path().is_ident("phases"))
+        .filter(|meta| !meta.path().is_ident("alias"))
+        .filter(|meta| !meta.path().is_ident("docs"))
+        .filter(|meta| !meta.path().is_ident("repr_transparent_states"))
+        .find(|meta| !meta.path().is_ident("__dependent"))
     {
         return Err(Error::new(meta.path().span(), "invalid attribute"));
     }
@@ -42,18 +56,53 @@ pub fn expand_item_struct_internal(
     let mut documentation = Documentation::default();
     documentation.configure_with_metas(&metas)?;
 
-    let mut stateset = Stateset::default().support("states").support("preset");
+    let mut stateset = Stateset::default()
+        .support("states")
+        .support("group")
+        .support("counter")
+        .support("phases")
+        .support("alias")
+        .support("preset");
     stateset.extend_with_metas(&metas)?;
 
     if documentation.description {
         item_struct.attrs.push(
             Description::new(&stateset)
                 .line(DescriptionLine::new("states").label("States"))
+                .line(DescriptionLine::new("group").label("Groups"))
+                .line(DescriptionLine::new("counter").label("Counters"))
+                .line(DescriptionLine::new("phases").label("Phases"))
+                .line(DescriptionLine::new("alias").label("Aliases"))
                 .line(DescriptionLine::new("preset").label("Preset"))
                 .generate(),
         );
     }
 
+    // The layout of the struct can be guaranteed to be the same for every states
+    // type, so it's reconstructed with a transmute rather than by moving each field.
+    let repr_transparent_states = metas
+        .iter()
+        .find(|meta| meta.path().is_ident("repr_transparent_states"))
+        .map(Meta::require_path_only)
+        .transpose()?
+        .is_some();
+
+    if repr_transparent_states {
+        // Validate the struct doesn't have its own representation.
+        if let Some(attr) = item_struct
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("repr"))
+        {
+            return Err(Error::new(
+                attr.path().span(),
+                "the representation is already set by `repr_transparent_states`",
+            ));
+        }
+
+        item_struct.attrs.push(parse_squote!(#[repr(C)]));
+    }
+
     // Validate there are parameters, since one must be designated.
     if item_struct.generics.params.is_empty() {
         return Err(Error::new(
@@ -62,453 +111,1097 @@ pub fn expand_item_struct_internal(
         ));
     }
 
-    let (designated_param_index, designating_attr_index) =
-        get_designated_indices(&item_struct.generics.params)?;
-    let designated_param =
-        item_struct.generics.params[designated_param_index].require_type_param_mut()?;
-
-    // Remove the designating attribute from the designated parameter.
-    designated_param.attrs.remove(designating_attr_index);
+    let alias_types = alias_types(
+        &item_struct.vis,
+        &item_struct.ident,
+        &item_struct.generics,
+        &stateset,
+    )?;
+
+    let accessor_impl = accessor_impl(&metas, &item_struct)?;
+
+    let designated_params = get_designated_params(&item_struct.generics.params)?;
+
+    // Validate the field types are the same for every states type, so the layout is
+    // too. Fields that depend on a state are, since the state is kept.
+    if repr_transparent_states {
+        let param_idents = designated_params
+            .iter()
+            .map(|designated| {
+                item_struct.generics.params[designated.param_index]
+                    .require_type_param()
+                    .map(|param| param.ident.clone())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for field in item_struct.fields.iter() {
+            if FieldAttrs::from_field(field)?.dependent_ty.is_some() {
+                continue;
+            }
 
-    // Add a phantom field for the generic parameter.
-    let phantom_ty: Type = parse_squote!(::std::marker::PhantomData<#{designated_param.ident}>);
+            let mut finder = FindTypeParam {
+                idents: &param_idents,
+                found: None,
+            };
+            finder.visit_type_mut(&mut field.ty.clone());
 
-    match &mut item_struct.fields {
-        Fields::Named(FieldsNamed { named, .. }) => {
-            named.push(parse_squote!(__states: #phantom_ty));
-        }
-        Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => {
-            unnamed.push(parse_squote!(#phantom_ty));
-        }
-        Fields::Unit => {
-            // If the struct is a unit struct, it will be changed to a tuple struct.
-            item_struct.fields = Fields::Unnamed(parse_squote!((#phantom_ty)));
+            if let Some(span) = finder.found {
+                return Err(Error::new(
+                    span,
+                    "field types cannot name the designated parameters with `repr_transparent_states`",
+                ));
+            }
         }
     }
 
-    let (impl_generics, ty_generics, where_clause) = item_struct.generics.split_for_impl();
+    replace_initial_defaults(&mut item_struct.generics, &stateset)?;
+    let (phantom_ty, re_params, mut re_predicates, re_generics) =
+        strip_designated_params(&mut item_struct.generics)?;
 
-    // The generic parameter added to track states must be changed when
-    // reconstructed.
-    let mut generics_clone = item_struct.generics.clone();
-    generics_clone.params[designated_param_index] = parse_squote!(__Re);
-    let re_generics = generics_clone.split_for_impl().1;
+    let (states_traits, dependent_re_predicates) =
+        replace_dependent_tys(&mut item_struct, &designated_params, &re_params, &stateset)?;
+    re_predicates.extend(dependent_re_predicates);
+
+    // Add a phantom field for the designated parameters.
+    add_phantom_field(&mut item_struct.fields, &phantom_ty);
+
+    let (impl_generics, ty_generics, where_clause) = item_struct.generics.split_for_impl();
+    let re_generics = re_generics.split_for_impl().1;
 
     // Collect the members and split off the phantom member, which is last.
     let members = item_struct.fields.members().collect_vec();
     let (member_phantom, members_rest) = members.split_last().unwrap();
 
-    Ok(squote! {
-        #item_struct
+    // The reconstructed states must satisfy the bounds of the designated parameters
+    // and keep the states that field types depend on.
+    let re_where_clause = (!re_predicates.is_empty()).then(|| squote!(where #(#re_predicates),*));
+
+    let pointer_reconstruct_fns = pointer_reconstruct_fns(
+        &re_params,
+        &squote!(#{item_struct.ident} #re_generics),
+        re_where_clause.as_ref(),
+    );
+
+    let reconstruct_body = if repr_transparent_states {
+        squote! {
+            // SAFETY: The struct is `repr(C)`, and the field types are validated to be the
+            // same for every states type, except for the phantom field, which is
+            // zero-sized. Field types that depend on a state are the same, since the state
+            // is kept.
+            unsafe { ::stated::__transmute(self) }
+        }
+    } else {
+        // The fields are read out of a `ManuallyDrop`, since they can't be moved out
+        // of a struct that implements `Drop`. It's dereferenced through a pointer,
+        // since `Deref` can't be called in a const fn.
+        squote! {
+            let this = ::std::mem::ManuallyDrop::new(self);
+
+            // SAFETY: `ManuallyDrop` is `repr(transparent)`. Each field is read once,
+            // and `this` is never dropped.
+            unsafe {
+                let this = &*(&raw const this).cast::<Self>();
 
-        impl #impl_generics #{item_struct.ident} #ty_generics #where_clause {
-            pub(crate) fn __reconstruct<__Re>(self) -> #{item_struct.ident} #re_generics {
                 #{item_struct.ident} {
-                    #(#members_rest: self.#members_rest,)*
+                    #(#members_rest: ::std::ptr::read(&this.#members_rest),)*
                     #member_phantom: ::std::marker::PhantomData,
                 }
             }
         }
+    };
+
+    Ok(squote! {
+        #item_struct
+
+        #(#alias_types)*
+
+        #(#states_traits)*
+
+        #accessor_impl
+
+        impl #impl_generics #{item_struct.ident} #ty_generics #where_clause {
+            pub(crate) const fn __reconstruct<#(#re_params),*>(self) -> #{item_struct.ident} #re_generics
+            #re_where_clause
+            {
+                #reconstruct_body
+            }
+
+            #pointer_reconstruct_fns
+        }
     })
 }
 
-pub fn expand_item_impl_internal(
+/// Generate the methods that reconstruct a type behind a `Box`, `Rc`, or `Arc`
+/// receiver into `re_ty`, which is generic over `re_params`.
+fn pointer_reconstruct_fns(
+    re_params: &[Ident],
+    re_ty: &TokenStream2,
+    re_where_clause: Option<&TokenStream2>,
+) -> TokenStream2 {
+    // The allocation is reused if the reconstructed type has the same layout,
+    // which is only not the case if field types depend on the states.
+    let reuse = squote! {
+        let raw = raw.cast::<#re_ty>();
+
+        // SAFETY: The allocation has the layout of the reconstructed type.
+        unsafe {
+            raw.write(re);
+            ::std::boxed::Box::from_raw(raw)
+        }
+    };
+
+    let realloc = squote! {
+        // SAFETY: `MaybeUninit` has the same layout, and doesn't drop the value.
+        ::std::mem::drop(unsafe {
+            ::std::boxed::Box::from_raw(raw.cast::<::std::mem::MaybeUninit<Self>>())
+        });
+
+        ::std::boxed::Box::new(re)
+    };
+
+    let box_body = squote! {
+        let raw = ::std::boxed::Box::into_raw(self);
+
+        // SAFETY: The pointer came from a box, and the value is only moved out once.
+        // The allocation is then either reused or freed without dropping the value
+        // again.
+        let re = unsafe { raw.read() }.__reconstruct();
+
+        if ::std::alloc::Layout::new::<Self>() == ::std::alloc::Layout::new::<#re_ty>() {
+            #reuse
+        } else {
+            #realloc
+        }
+    };
+
+    let box_fn = squote! {
+        pub(crate) fn __reconstruct_box<#(#re_params),*>(
+            self: ::std::boxed::Box<Self>,
+        ) -> ::std::boxed::Box<#re_ty>
+        #re_where_clause
+        {
+            #box_body
+        }
+    };
+
+    // Shared values can't change states, so they are returned as is.
+    let rc_fn = squote! {
+        pub(crate) fn __reconstruct_rc<#(#re_params),*>(
+            self: ::std::rc::Rc<Self>,
+        ) -> ::std::result::Result<::std::rc::Rc<#re_ty>, ::std::rc::Rc<Self>>
+        #re_where_clause
+        {
+            ::std::rc::Rc::try_unwrap(self).map(|this| ::std::rc::Rc::new(this.__reconstruct()))
+        }
+    };
+
+    let arc_fn = squote! {
+        pub(crate) fn __reconstruct_arc<#(#re_params),*>(
+            self: ::std::sync::Arc<Self>,
+        ) -> ::std::result::Result<::std::sync::Arc<#re_ty>, ::std::sync::Arc<Self>>
+        #re_where_clause
+        {
+            ::std::sync::Arc::try_unwrap(self)
+                .map(|this| ::std::sync::Arc::new(this.__reconstruct()))
+        }
+    };
+
+    squote! {
+        #box_fn
+
+        #rc_fn
+
+        #arc_fn
+    }
+}
+
+pub fn expand_item_enum_internal(
     metas: Punctuated<Meta, Token![,]>,
-    mut item_impl: ItemImpl,
+    mut item_enum: ItemEnum,
 ) -> Result<TokenStream2> {
     // Validate all attributes in the metas are supported.
     if let Some(meta) = metas
         .iter()
         .filter(|meta| !meta.path().is_ident("states"))
         .filter(|meta| !meta.path().is_ident("preset"))
+        .filter(|meta| !meta.path().is_ident("group"))
+        .filter(|meta| !meta.path().is_ident("counter"))
+        .filter(|meta| !meta.path().is_ident("phases"))
+        .filter(|meta| !meta.path().is_ident("alias"))
         .find(|meta| !meta.path().is_ident("docs"))
     {
         return Err(Error::new(meta.path().span(), "invalid attribute"));
     }
 
-    // Validate the implementation isn't for a trait.
-    if let Some((_, trait_, _)) = item_impl.trait_.as_ref() {
-        return Err(Error::new(trait_.span(), "trait impls are not supported"));
-    }
-
     let mut documentation = Documentation::default();
     documentation.configure_with_metas(&metas)?;
 
-    let mut stateset = Stateset::default().support("states").support("preset");
+    let mut stateset = Stateset::default()
+        .support("states")
+        .support("group")
+        .support("counter")
+        .support("phases")
+        .support("alias")
+        .support("preset");
     stateset.extend_with_metas(&metas)?;
 
-    // Validate at least one state was declared.
-    if stateset["states"].is_empty() {
-        return Err(Error::new(metas.span(), "no states were declared"));
-    }
-
-    // Validate there are no duplicate declared states.
-    if let Some(state) = stateset["states"].iter().duplicates().next() {
-        return Err(Error::new(state.span(), "state is already declared"));
-    }
-
-    // Validate there are no duplicate preset states.
-    if let Some(state) = stateset["preset"].iter().duplicates().next() {
-        return Err(Error::new(state.span(), "state is already preset"));
-    }
-
-    // Validate the preset states are a subset of the declared states.
-    if let Some(state) = stateset["preset"]
-        .iter()
-        .find(|state| !stateset["states"].contains(state))
-    {
-        return Err(Error::new(
-            state.span(),
-            "preset state is not a declared state",
-        ));
+    if documentation.description {
+        item_enum.attrs.push(
+            Description::new(&stateset)
+                .line(DescriptionLine::new("states").label("States"))
+                .line(DescriptionLine::new("group").label("Groups"))
+                .line(DescriptionLine::new("counter").label("Counters"))
+                .line(DescriptionLine::new("phases").label("Phases"))
+                .line(DescriptionLine::new("alias").label("Aliases"))
+                .line(DescriptionLine::new("preset").label("Preset"))
+                .generate(),
+        );
     }
 
     // Validate there are parameters, since one must be designated.
-    if item_impl.generics.params.is_empty() {
+    if item_enum.generics.params.is_empty() {
         return Err(Error::new(
-            item_impl.impl_token.span(),
+            item_enum.enum_token.span(),
             "expected a designated parameter",
         ));
     }
 
-    // Get the designated indices.
-    let (designated_param_index, designating_attr_index) =
-        get_designated_indices(&item_impl.generics.params)?;
-    let designated_param =
-        item_impl.generics.params[designated_param_index].require_type_param_mut()?;
-    let designated_param_ident = designated_param.ident.clone();
-
-    // Remove the designating attribute from the designated parameter.
-    designated_param.attrs.remove(designating_attr_index);
-
-    // Normally, validating the designated argument is done while expanding
-    // implementation items. If there are no impl items in the impl block, the
-    // designated argument should still be validated. That is done here.
-    if item_impl.items.is_empty() {
-        let args = item_impl
-            .self_ty
-            .require_path()?
-            .last()?
-            .arguments
-            .require_angle_bracketed()?;
+    let alias_types = alias_types(
+        &item_enum.vis,
+        &item_enum.ident,
+        &item_enum.generics,
+        &stateset,
+    )?;
 
-        // Catches `<>`, which is empty but still considered angle-bracketed.
-        if args.args.is_empty() {
+    for variant in item_enum.variants.iter() {
+        // Validate there are no discriminants, since every variant gets a field.
+        if let Some((_, discriminant)) = &variant.discriminant {
             return Err(Error::new(
-                args.lt_token.span(),
-                "an argument must match the designated parameter",
+                discriminant.span(),
+                "explicit discriminants are not supported on enums",
             ));
         }
 
-        // Validate the argument. Throw away the return value.
-        find_designated_arg(&args.args, &designated_param_ident)?;
+        // Validate the fields have no field attributes, which only structs support.
+        if let Some(attr) = variant
+            .fields
+            .iter()
+            .flat_map(|field| &field.attrs)
+            .find(|attr| attr.path().is_ident("stated"))
+        {
+            return Err(Error::new(
+                attr.span(),
+                "accessors and dependent fields are not supported on enums",
+            ));
+        }
     }
 
-    let impl_items = mem::take(&mut item_impl.items);
-
-    // Rename the variable for clarity. This will act as a template when needed. It
-    // has no impl items and the designating attribute was removed, making it a
-    // completely valid impl block.
-    let item_impl_template = item_impl;
+    replace_initial_defaults(&mut item_enum.generics, &stateset)?;
+    let (phantom_ty, re_params, re_predicates, re_generics) =
+        strip_designated_params(&mut item_enum.generics)?;
 
-    let mut pretty_item_impl = item_impl_template.clone();
+    // Add a phantom field for the designated parameters to every variant.
+    for variant in item_enum.variants.iter_mut() {
+        add_phantom_field(&mut variant.fields, &phantom_ty);
+    }
 
-    let mut expansions = Vec::new();
+    let (impl_generics, ty_generics, where_clause) = item_enum.generics.split_for_impl();
+    let re_generics = re_generics.split_for_impl().1;
 
-    for mut impl_item in impl_items {
-        let ruleset_attrs = impl_item
-            .require_fn_mut()?
-            .attrs
-            .extract_if(.., |attr| attr.path().is_ident("stated"))
-            .collect_vec();
+    let enum_ident = &item_enum.ident;
 
-        if ruleset_attrs.is_empty() {
-            return Err(Error::new(impl_item.span(), "no ruleset is specified"));
-        }
+    let re_where_clause = (!re_predicates.is_empty()).then(|| squote!(where #(#re_predicates),*));
 
-        for ruleset_attr in ruleset_attrs {
-            let mut ruleset = Stateset::default()
-                .support("assert")
-                .support("reject")
-                .support("assign")
-                .support("delete");
+    let pointer_reconstruct_fns = pointer_reconstruct_fns(
+        &re_params,
+        &squote!(#enum_ident #re_generics),
+        re_where_clause.as_ref(),
+    );
 
-            // Validate the ruleset attribute is not a name-value.
-            ruleset_attr.meta.forbid_name_value()?;
+    // Each variant is reconstructed in its own match arm. Braced syntax is used for
+    // all variants, since it also works for tuple variants. The fields are read
+    // through references, since they can't be moved out of an enum that
+    // implements `Drop`.
+    let arms = item_enum.variants.iter().map(|variant| {
+        let variant_ident = &variant.ident;
 
-            if let Meta::List(MetaList { tokens, .. }) = ruleset_attr.meta {
-                let metas = Punctuated::<Meta, Token![,]>::parse_terminated.parse2(tokens)?;
+        // Collect the members and split off the phantom member, which is last.
+        let members = variant.fields.members().collect_vec();
+        let (member_phantom, members_rest) = members.split_last().unwrap();
+        let bindings = (0..members_rest.len()).map(|index| format_ident!("__{index}"));
+        let bindings_clone = bindings.clone();
 
-                // Validate all attributes in the metas are supported.
-                if let Some(meta) = metas
-                    .iter()
-                    .filter(|meta| !meta.path().is_ident("assert"))
-                    .filter(|meta| !meta.path().is_ident("reject"))
-                    .filter(|meta| !meta.path().is_ident("assign"))
-                    .find(|meta| !meta.path().is_ident("delete"))
-                {
-                    return Err(Error::new(meta.path().span(), "invalid attribute"));
+        squote! {
+            #enum_ident::#variant_ident { #(#members_rest: #bindings,)* .. } => {
+                #enum_ident::#variant_ident {
+                    #(#members_rest: ::std::ptr::read(#bindings_clone),)*
+                    #member_phantom: ::std::marker::PhantomData,
                 }
-
-                ruleset.extend_with_metas(&metas)?;
             }
+        }
+    });
 
-            // Validate the asserted states contain no duplicates.
-            if let Some(state) = ruleset["assert"].iter().duplicates().next() {
-                return Err(Error::new(state.span(), "state is already asserted"));
-            }
+    Ok(squote! {
+        #item_enum
 
-            // Validate the rejected states contain no duplicates.
-            if let Some(state) = ruleset["reject"].iter().duplicates().next() {
-                return Err(Error::new(state.span(), "state is already rejected"));
-            }
+        #(#alias_types)*
 
-            // Validate the assigned states contain no duplicates.
-            if let Some(state) = ruleset["assign"].iter().duplicates().next() {
-                return Err(Error::new(state.span(), "state is already assigned"));
-            }
+        impl #impl_generics #enum_ident #ty_generics #where_clause {
+            pub(crate) const fn __reconstruct<#(#re_params),*>(self) -> #enum_ident #re_generics
+            #re_where_clause
+            {
+                let this = ::std::mem::ManuallyDrop::new(self);
 
-            // Validate the deleted states contain no duplicates.
-            if let Some(state) = ruleset["delete"].iter().duplicates().next() {
-                return Err(Error::new(state.span(), "state is already deleted"));
+                // SAFETY: `ManuallyDrop` is `repr(transparent)`. The fields of one variant
+                // are each read once, and `this` is never dropped.
+                unsafe {
+                    match &*(&raw const this).cast::<Self>() {
+                        #(#arms)*
+                    }
+                }
             }
 
-            // Validate the asserted states are declared.
-            if let Some(state) = ruleset["assert"]
-                .iter()
-                .find(|state| !stateset["states"].contains(state))
-            {
-                return Err(Error::new(state.span(), "asserted state is not declared"));
-            }
+            #pointer_reconstruct_fns
+        }
+    })
+}
 
-            // Validate the rejected states are declared.
-            if let Some(state) = ruleset["reject"]
-                .iter()
-                .find(|state| !stateset["states"].contains(state))
-            {
-                return Err(Error::new(state.span(), "rejected state is not declared"));
-            }
+/// A designated parameter of an impl block and the group of states it tracks.
+struct Group {
+    /// The group named by the designating attribute.
+    name: Option<Ident>,
+    /// The index of the designated parameter.
+    param_index: usize,
+    /// The ident of the designated parameter.
+    param_ident: Ident,
+    /// The index of the argument matching the designated parameter.
+    arg_index: usize,
+}
 
-            // Validate the asserted states are declared.
-            if let Some(state) = ruleset["assign"]
-                .iter()
-                .find(|state| !stateset["states"].contains(state))
-            {
-                return Err(Error::new(state.span(), "assigned state is not declared"));
-            }
+impl Group {
+    /// Get the slots of the states type for the declarations in `stateset` that
+    /// belong to the group.
+    fn slots<'a>(&self, stateset: &'a Stateset) -> impl Iterator<Item = Slot<'a>> {
+        let name = self.name.clone();
 
-            // Validate the asserted states are declared.
-            if let Some(state) = ruleset["delete"]
-                .iter()
-                .find(|state| !stateset["states"].contains(state))
-            {
-                return Err(Error::new(state.span(), "deleted state is not declared"));
-            }
+        let flags = stateset["states"].iter().map(|state| match state.ty {
+            Some(_) => Slot::Parameterized(state),
+            None => Slot::Flag(state),
+        });
+        let exclusives = stateset["group"].iter().map(Slot::Exclusive);
+        let counters = stateset["counter"].iter().map(Slot::Counter);
+        let phases = stateset["phases"].iter().map(Slot::Phases);
+
+        flags
+            .chain(exclusives)
+            .chain(counters)
+            .chain(phases)
+            .filter(move |slot| slot.state().group == name)
+    }
+}
 
-            // Validate the asserted and rejected states are disjoint.
-            if let Some(state) = ruleset["reject"]
-                .iter()
-                .find(|state| ruleset["assert"].contains(state))
-            {
-                return Err(Error::new(
-                    state.span(),
-                    "rejected state cannot also be asserted",
-                ));
-            }
+/// A slot in the states type.
+#[derive(Clone, Copy)]
+enum Slot<'a> {
+    /// A state, which is either enabled or disabled.
+    Flag(&'a State),
+    /// A parameterized state, which is either disabled or enabled with the type
+    /// it carries.
+    Parameterized(&'a State),
+    /// An exclusive group, which holds its enabled choice.
+    Exclusive(&'a State),
+    /// A counter, which holds its count and, if it has a max, the increments
+    /// left.
+    Counter(&'a State),
+    /// The phases, which hold the current phase and the phases after it.
+    Phases(&'a State),
+}
 
-            // Validate the assigned and deleted states are disjoint.
-            if let Some(state) = ruleset["delete"]
-                .iter()
-                .find(|state| ruleset["assign"].contains(state))
-            {
-                return Err(Error::new(
-                    state.span(),
-                    "deleted state cannot also be assigned",
-                ));
-            }
+impl<'a> Slot<'a> {
+    /// Get the declaration of the slot.
+    fn state(self) -> &'a State {
+        match self {
+            Self::Flag(state)
+            | Self::Parameterized(state)
+            | Self::Exclusive(state)
+            | Self::Counter(state)
+            | Self::Phases(state) => state,
+        }
+    }
+}
 
-            // Validate the asserted and assigned states are disjoint.
-            if let Some(state) = ruleset["assign"]
-                .iter()
-                .find(|state| ruleset["assert"].contains(state))
-            {
-                // TODO(blocked): Emit a warning once procedural macro diagnostics is
-                // stabilized. Tracking issue: https://github.com/rust-lang/rust/issues/54140.
-                return Err(Error::new(
-                    state.span(),
-                    "asserted state doesn't need to be assigned",
-                ));
-            }
+/// Shared information for expanding the impl items of an impl block.
+struct ImplContext {
+    documentation: Documentation,
+    stateset: Stateset,
+    /// The variants, if the impl block is for an enum.
+    variants: Option<Vec<Ident>>,
+    /// The states that field types depend on.
+    dependent: Vec<State>,
+    /// The designated groups, ordered by parameter index.
+    groups: Vec<Group>,
+    /// The ident of the type the impl block is for.
+    self_ident: Ident,
+}
+
+impl ImplContext {
+    /// Get a visitor that replaces `_` in types with `tys`, which are ordered by
+    /// group.
+    fn type_infer_replacer(&self, mut tys: Vec<Type>) -> Box<dyn VisitMut> {
+        // With a single group, every `_` is replaced, as it always has been.
+        if tys.len() == 1 {
+            return Box::new(ReplaceTypeInfer(tys.remove(0)));
+        }
 
-            // Validate the rejected and deleted states are disjoint.
-            if let Some(state) = ruleset["delete"]
+        // With multiple groups, `_` is replaced by position in the type's arguments.
+        Box::new(ReplaceTypeInferInArgs {
+            ident: self.self_ident.clone(),
+            replacements: self
+                .groups
                 .iter()
-                .find(|state| ruleset["reject"].contains(state))
-            {
-                // TODO(blocked): Emit a warning once procedural macro diagnostics is
-                // stabilized. Tracking issue: https://github.com/rust-lang/rust/issues/54140.
-                return Err(Error::new(
-                    state.span(),
-                    "rejected state doesn't need to be deleted",
-                ));
-            }
+                .map(|group| group.arg_index)
+                .zip(tys)
+                .collect(),
+        })
+    }
 
-            // Clone the impl block. Each function will go in its own block due to differing
-            // generics.
-            let mut item_impl = item_impl_template.clone();
-            let mut impl_item = impl_item.clone();
-            let associated_fn = impl_item.require_fn_mut()?;
+    /// Add the predicates that the states types must satisfy for the field types
+    /// to depend on their states to `generics`. The states that are still generic
+    /// must be bits.
+    fn add_dependent_predicates(&self, generics: &mut Generics, ruleset: &Stateset) {
+        let generic_states = self
+            .dependent
+            .iter()
+            .filter(|state| !ruleset["assert"].contains(state))
+            .filter(|state| !ruleset["reject"].contains(state))
+            .map(|state| &state.ident)
+            .collect_vec();
 
-            if documentation.description {
-                associated_fn.attrs.push(
-                    Description::new(&ruleset)
-                        .line(DescriptionLine::new("assert").label("Assert"))
-                        .line(DescriptionLine::new("reject").label("Reject"))
-                        .line(DescriptionLine::new("assign").label("Assign"))
-                        .line(DescriptionLine::new("delete").label("Delete"))
-                        .generate(),
-                );
-            }
+        if generic_states.is_empty() {
+            return;
+        }
 
-            let item_impl_path = item_impl.self_ty.require_path_mut()?;
+        let predicates = &mut generics.make_where_clause().predicates;
 
-            let args = item_impl_path
-                .last_mut()?
-                .arguments
-                .require_angle_bracketed_mut()?;
+        for ident in generic_states {
+            predicates.push(parse_squote!(#ident: ::stated::Bit));
+        }
+    }
 
-            // Catches `<>`, which is empty but still considered angle-bracketed.
-            if args.args.is_empty() {
-                return Err(Error::new(
-                    args.lt_token.span(),
-                    "an argument must match the designated parameter",
-                ));
+    /// Add the predicates that bound the designated parameters by the traits
+    /// their field types read the states with to `generics`.
+    fn add_states_trait_predicates(&self, generics: &mut Generics) {
+        for group in self.groups.iter() {
+            if !self.dependent.iter().any(|state| state.group == group.name) {
+                continue;
             }
 
-            let args = &mut args.args;
-            let designated_arg_index = find_designated_arg(args, &designated_param_ident)?;
+            let trait_ident = states_trait_ident(&self.self_ident, group.name.as_ref());
 
-            if !documentation.ugly {
-                let mut pretty_associated_fn = associated_fn.clone();
+            generics
+                .make_where_clause()
+                .predicates
+                .push(parse_squote!(#{group.param_ident}: #trait_ident));
+        }
+    }
 
-                // Replace `_` in the return type with the designated parameter's ident.
-                ReplaceTypeInfer(parse_squote!(#designated_param_ident))
-                    .visit_return_type_mut(&mut pretty_associated_fn.sig.output);
-                pretty_associated_fn.block = parse_squote!({ unreachable!() });
+    /// Get a visitor that replaces the designated parameters with `tys`, which are
+    /// ordered by group.
+    fn type_param_replacer(&self, tys: Vec<Type>) -> ReplaceTypeParam {
+        ReplaceTypeParam {
+            replacements: self
+                .groups
+                .iter()
+                .map(|group| group.param_ident.clone())
+                .zip(tys)
+                .collect(),
+        }
+    }
 
-                pretty_item_impl
-                    .items
-                    .push(ImplItem::Fn(pretty_associated_fn));
+    /// Translate the predicates of `generics` that name the designated parameters
+    /// onto `in_tys`, which are ordered by group. Predicates that bound a
+    /// designated parameter itself are also added for `out_tys`, since the
+    /// outgoing types must satisfy the bounds as well.
+    fn translate_param_predicates(
+        &self,
+        generics: &mut Generics,
+        in_tys: &[Type],
+        out_tys: Option<&[Type]>,
+    ) {
+        let Some(where_clause) = &mut generics.where_clause else {
+            return;
+        };
+
+        let mut out_predicates: Vec<WherePredicate> = Vec::new();
+
+        if let Some(out_tys) = out_tys {
+            for predicate in where_clause.predicates.iter() {
+                let WherePredicate::Type(PredicateType {
+                    lifetimes: None,
+                    bounded_ty,
+                    bounds,
+                    ..
+                }) = predicate
+                else {
+                    continue;
+                };
+
+                if let Some(index) = self
+                    .groups
+                    .iter()
+                    .position(|group| bounded_ty.is_param(&group.param_ident))
+                {
+                    let out_ty = &out_tys[index];
+                    out_predicates.push(parse_squote!(#out_ty: #bounds));
+                }
             }
+        }
 
-            if let Some(receiver) = associated_fn.sig.receiver() {
-                let receiver_span = receiver.span();
-
-                let replace_with = stateset["states"]
-                    .iter()
-                    .filter(|state| !ruleset["assert"].contains(state))
-                    .filter(|state| !ruleset["reject"].contains(state))
-                    .map(|state| parse_squote!(#state));
-
-                item_impl.generics.params.call(|params| {
-                    params.splice(
-                        designated_param_index..(designated_param_index + 1),
-                        replace_with,
-                    );
-                });
+        self.type_param_replacer(in_tys.to_vec())
+            .visit_where_clause_mut(where_clause);
+        where_clause.predicates.extend(out_predicates);
+    }
 
-                let states_in_ty = stateset["states"].iter().map(|state| -> Type {
-                    if ruleset["assert"].contains(state) {
-                        parse_squote!(::stated::Y)
-                    } else if ruleset["reject"].contains(state) {
-                        parse_squote!(::stated::N)
-                    } else {
-                        parse_squote!(#state)
-                    }
-                });
+    /// Get the types of the designated parameters, ordered by group.
+    fn param_tys(&self) -> Vec<Type> {
+        self.groups
+            .iter()
+            .map(|group| parse_squote!(#{group.param_ident}))
+            .collect()
+    }
+}
 
-                // Replace the designated argument with the ingoing type.
-                args[designated_arg_index] = parse_squote!((#(#states_in_ty),*));
-
-                let states_out_ty = stateset["states"].iter().map(|state| -> Type {
-                    if ruleset["assign"].contains(state) {
-                        parse_squote!(::stated::Y)
-                    } else if ruleset["delete"].contains(state) {
-                        parse_squote!(::stated::N)
-                    } else if ruleset["assert"].contains(state) {
-                        parse_squote!(::stated::Y)
-                    } else if ruleset["reject"].contains(state) {
-                        parse_squote!(::stated::N)
-                    } else {
-                        parse_squote!(#state)
-                    }
-                });
+pub fn expand_item_impl_internal(
+    metas: Punctuated<Meta, Token![,]>,
+    mut item_impl: ItemImpl,
+) -> Result<TokenStream2> {
+    // Validate all attributes in the metas are supported.
+    if let Some(meta) = metas
+        .iter()
+        .filter(|meta| !meta.path().is_ident("states"))
+        .filter(|meta| !meta.path().is_ident("preset"))
+        .filter(|meta| !meta.path().is_ident("group"))
+        .filter(|meta| !meta.path().is_ident("counter"))
+        .filter(|meta| !meta.path().is_ident("phases"))
+        .filter(|meta| !meta.path().is_ident("alias"))
+        .filter(|meta| !meta.path().is_ident("docs"))
+        .filter(|meta| !meta.path().is_ident("__variants"))
+        .filter(|meta| !meta.path().is_ident("__dependent"))
+        // The layout option is exported with the struct's metas, but only affects
+        // the struct.
+        .find(|meta| !meta.path().is_ident("repr_transparent_states"))
+    {
+        return Err(Error::new(meta.path().span(), "invalid attribute"));
+    }
 
-                // Replace the designated argument with the outgoing type.
-                ReplaceTypeInfer(parse_squote!((#(#states_out_ty),*)))
-                    .visit_return_type_mut(&mut associated_fn.sig.output);
+    let mut documentation = Documentation::default();
+    documentation.configure_with_metas(&metas)?;
 
-                ReplaceExprInfer(parse_squote!(@receiver_span=> self.__reconstruct()))
-                    .visit_block_mut(&mut associated_fn.block);
-            } else {
-                if let Some(state) = ruleset["assert"]
-                    .first()
-                    .or_else(|| ruleset["reject"].first())
-                {
-                    return Err(Error::new(
-                        state.span(),
-                        "only methods can assert or reject states",
-                    ));
-                }
+    let mut stateset = Stateset::default()
+        .support("states")
+        .support("group")
+        .support("counter")
+        .support("phases")
+        .support("alias")
+        .support("preset");
+    stateset.extend_with_metas(&metas)?;
 
-                // Replace the designated argument with the stateless type.
-                args[designated_arg_index] = parse_squote!(::stated::__);
-
-                // Remove the designated parameter.
-                item_impl
-                    .generics
-                    .params
-                    .call(|params| params.remove(designated_param_index));
-
-                let states_out_ty = stateset["states"].iter().map(|state| -> Type {
-                    if ruleset["assign"].contains(state) {
-                        parse_squote!(::stated::Y)
-                    } else if ruleset["delete"].contains(state) {
-                        parse_squote!(::stated::N)
-                    } else if stateset["preset"].contains(state) {
-                        parse_squote!(::stated::Y)
-                    } else {
-                        parse_squote!(::stated::N)
-                    }
-                });
+    // Enums export their variants, which are constructed and matched instead of
+    // the enum itself.
+    let variants = metas
+        .iter()
+        .find(|meta| meta.path().is_ident("__variants"))
+        .map(|meta| {
+            let mut variants = Vec::new();
 
-                // Replace `_` in the return type with the states-out type.
-                ReplaceTypeInfer(parse_squote!((#(#states_out_ty),*)))
-                    .visit_return_type_mut(&mut associated_fn.sig.output);
-            }
+            meta.require_list()?.parse_nested_meta(|meta| {
+                variants.push(meta.path.require_ident().cloned()?);
+                Ok(())
+            })?;
 
-            AddFieldInStructConstruction {
-                path: &item_impl_path.path,
-                field_member: parse_squote!(__states),
-                field_expr: parse_squote!(::std::marker::PhantomData),
-            }
-            .visit_block_mut(&mut associated_fn.block);
+            Result::Ok(variants)
+        })
+        .transpose()?;
 
-            item_impl.items.push(impl_item);
+    // Structs export the states their field types depend on.
+    let mut dependent = Vec::new();
 
-            if documentation.ugly {
-                expansions.push(squote!(#item_impl));
-            } else {
-                expansions.push(squote! {
-                    #[cfg(not(doc))]
-                    #item_impl
-                });
-            }
-        }
+    if let Some(meta) = metas
+        .iter()
+        .find(|meta| meta.path().is_ident("__dependent"))
+    {
+        meta.require_list()?.parse_nested_meta(|meta| {
+            dependent.push(State::from_path(&meta.path)?);
+            Ok(())
+        })?;
     }
 
-    if !documentation.ugly {
-        expansions.push(squote! {
+    // Validate at least one state was declared.
+    if declarations(&stateset).next().is_none() {
+        return Err(Error::new(metas.span(), "no states were declared"));
+    }
+
+    // Validate expressions are only used in rules.
+    if let Some(expr) = ["states", "group", "preset"]
+        .into_iter()
+        .find_map(|kind| stateset.exprs(kind).first())
+    {
+        return Err(Error::new(
+            expr.span(),
+            "expressions can only be used in rules",
+        ));
+    }
+
+    // Validate only exclusive groups have choices.
+    if let Some(choice) = stateset["states"]
+        .iter()
+        .find_map(|state| state.choices.first())
+    {
+        return Err(Error::new(
+            choice.span(),
+            "only exclusive groups can have choices",
+        ));
+    }
+
+    // Validate parameterized states are declared with a type parameter, which
+    // names the carried type in rules.
+    if let Some(ty) = stateset["states"]
+        .iter()
+        .filter_map(|state| state.ty.as_deref())
+        .find(|ty| param_ident(ty).is_none())
+    {
+        return Err(Error::new(ty.span(), "expected a type parameter"));
+    }
+
+    // Validate only states are parameterized.
+    if let Some(state) = stateset["group"].iter().find(|state| state.ty.is_some()) {
+        return Err(Error::new(state.span(), "only states can be parameterized"));
+    }
+
+    // Validate every exclusive group has choices.
+    if let Some(state) = stateset["group"]
+        .iter()
+        .find(|state| state.choices.is_empty())
+    {
+        return Err(Error::new(
+            state.span(),
+            "expected choices for the exclusive group",
+        ));
+    }
+
+    // Validate there are no duplicate choices in an exclusive group.
+    if let Some(choice) = stateset["group"]
+        .iter()
+        .find_map(|state| state.choices.iter().duplicates().next())
+    {
+        return Err(Error::new(choice.span(), "choice is already declared"));
+    }
+
+    // Validate every counter with a max can be incremented.
+    if let Some(bound) = stateset["counter"]
+        .iter()
+        .filter_map(|state| state.bound.as_ref())
+        .find(|bound| bound.value == 0)
+    {
+        return Err(Error::new(bound.span, "counter max must be at least one"));
+    }
+
+    // Validate the phases are declared at most once, since they share a slot.
+    if let Some(phases) = stateset["phases"].get(1) {
+        return Err(Error::new(phases.span(), "phases are already declared"));
+    }
+
+    // Validate the phases are unique, and don't share an ident with another
+    // declaration, since rules name phases directly.
+    if let Some(phase) = stateset["phases"].iter().find_map(|phases| {
+        phases
+            .choices
+            .iter()
+            .enumerate()
+            .find_map(|(index, phase)| {
+                let is_duplicate = phases.choices[..index].contains(phase)
+                    || declarations(&stateset)
+                        .chain(&stateset["alias"])
+                        .any(|state| state.ident == *phase);
+
+                is_duplicate.then_some(phase)
+            })
+    }) {
+        return Err(Error::new(phase.span(), "phase is already declared"));
+    }
+
+    validate_aliases(&stateset)?;
+
+    // Validate there are no duplicate declared states. States in different groups
+    // can't share an ident either, since the ident names a generic parameter.
+    if let Some(state) = declarations(&stateset)
+        .chain(&stateset["alias"])
+        .duplicates_by(|state| &state.ident)
+        .next()
+    {
+        return Err(Error::new(state.span(), "state is already declared"));
+    }
+
+    // Aliases and substates can be preset, just like in rules.
+    let aliases = stateset["alias"].clone();
+    stateset.expand_aliases(&aliases);
+
+    let declared = stateset["states"].clone();
+    stateset.expand_substates(&declared);
+
+    // Validate there are no duplicate preset states. An exclusive group can only
+    // be preset to one choice.
+    if let Some(state) = stateset["preset"]
+        .iter()
+        .duplicates_by(|state| (&state.group, &state.ident))
+        .next()
+    {
+        return Err(Error::new(state.span(), "state is already preset"));
+    }
+
+    // Validate at most one phase is preset.
+    if let Some(state) = stateset["preset"]
+        .iter()
+        .filter(|state| find_phases(state, &stateset).is_some())
+        .nth(1)
+    {
+        return Err(Error::new(state.span(), "phase is already preset"));
+    }
+
+    // Validate the preset states are a subset of the declared states.
+    if let Some(state) = stateset["preset"]
+        .iter()
+        .find(|state| state.bound.is_some() || !is_declared(state, &stateset))
+    {
+        return Err(Error::new(
+            state.span(),
+            "preset state is not a declared state",
+        ));
+    }
+
+    // Validate only parameterized states are preset with a type, and always are.
+    for state in stateset["preset"].iter() {
+        match (find_parameterized(state, &stateset), carried_ty(state)) {
+            (Some(_), None) => {
+                return Err(Error::new(
+                    state.span(),
+                    "expected a type for the parameterized state",
+                ));
+            }
+            (None, Some(_)) => {
+                return Err(Error::new(
+                    state.span(),
+                    "only parameterized states can carry a type",
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    // Validate there are parameters, since one must be designated.
+    if item_impl.generics.params.is_empty() {
+        return Err(Error::new(
+            item_impl.impl_token.span(),
+            "expected a designated parameter",
+        ));
+    }
+
+    let args = item_impl
+        .self_ty
+        .require_path()?
+        .last()?
+        .arguments
+        .require_angle_bracketed()?;
+
+    // Catches `<>`, which is empty but still considered angle-bracketed.
+    if args.args.is_empty() {
+        return Err(Error::new(
+            args.lt_token.span(),
+            "an argument must match the designated parameter",
+        ));
+    }
+
+    let mut groups = Vec::new();
+
+    for Designated {
+        param_index,
+        attr_index,
+        group,
+    } in get_designated_params(&item_impl.generics.params)?
+    {
+        let designated_param = item_impl.generics.params[param_index].require_type_param_mut()?;
+
+        // Remove the designating attribute from the designated parameter.
+        designated_param.attrs.remove(attr_index);
+
+        // Move the bounds of the designated parameter into the where clause, where
+        // they are translated onto the states types.
+        let bounds = mem::take(&mut designated_param.bounds);
+        designated_param.colon_token = None;
+        let designated_param = designated_param.clone();
+
+        if !bounds.is_empty() {
+            item_impl
+                .generics
+                .make_where_clause()
+                .predicates
+                .push(parse_squote!(#{designated_param.ident}: #bounds));
+        }
+
+        // Validate the group has declared states.
+        if !declarations(&stateset).any(|state| state.group == group) {
+            let span = match &group {
+                Some(group) => group.span(),
+                None => designated_param.ident.span(),
+            };
+
+            return Err(Error::new(span, "no states were declared for the group"));
+        }
+
+        groups.push(Group {
+            arg_index: find_designated_arg(&args.args, &designated_param.ident)?,
+            param_ident: designated_param.ident.clone(),
+            param_index,
+            name: group,
+        });
+    }
+
+    // Validate every declared state belongs to a designated group.
+    if let Some(state) =
+        declarations(&stateset).find(|state| !groups.iter().any(|group| group.name == state.group))
+    {
+        return Err(Error::new(
+            state.span(),
+            "state does not belong to a designated group",
+        ));
+    }
+
+    let self_ident = item_impl.self_ty.require_path()?.last()?.ident.clone();
+
+    let mut impl_ruleset_attrs = item_impl
+        .attrs
+        .extract_if(.., |attr| attr.path().is_ident("stated"))
+        .collect_vec();
+
+    // Validate there is at most one impl ruleset.
+    if let Some(impl_ruleset_attr) = impl_ruleset_attrs.get(1) {
+        return Err(Error::new(
+            impl_ruleset_attr.span(),
+            "impl blocks cannot have more than one ruleset",
+        ));
+    }
+
+    // The impl ruleset applies to every associated function in the impl block.
+    let impl_ruleset = match impl_ruleset_attrs.pop() {
+        Some(impl_ruleset_attr) => parse_ruleset(impl_ruleset_attr, &stateset)?,
+        None => new_ruleset(),
+    };
+
+    // Validate the impl ruleset doesn't transition states.
+    if let Some(span) = find_transition(&impl_ruleset) {
+        return Err(Error::new(
+            span,
+            "impl blocks can only assert or reject states",
+        ));
+    }
+
+    if documentation.description && !impl_ruleset.values().all(Vec::is_empty) {
+        item_impl.attrs.push(describe_ruleset(&impl_ruleset));
+    }
+
+    let context = ImplContext {
+        documentation,
+        stateset,
+        variants,
+        dependent,
+        groups,
+        self_ident,
+    };
+
+    // A type can only implement a trait once, so trait impls are expanded into a
+    // single impl block.
+    if item_impl.trait_.is_some() {
+        return expand_item_impl_trait(&context, impl_ruleset, item_impl);
+    }
+
+    let impl_items = mem::take(&mut item_impl.items);
+
+    // Rename the variable for clarity. This will act as a template when needed. It
+    // has no impl items and the designating attributes were removed, making it a
+    // completely valid impl block.
+    let item_impl_template = item_impl;
+
+    let mut pretty_item_impl = item_impl_template.clone();
+    context.add_states_trait_predicates(&mut pretty_item_impl.generics);
+
+    let mut expansions = Vec::new();
+
+    for mut impl_item in impl_items {
+        let ruleset_attrs = impl_item
+            .require_fn_mut()?
+            .attrs
+            .extract_if(.., |attr| attr.path().is_ident("stated"))
+            .collect_vec();
+
+        if ruleset_attrs.is_empty() {
+            return Err(Error::new(impl_item.span(), "no ruleset is specified"));
+        }
+
+        // Name the impl type instead of `Self`, so its designated arguments are
+        // replaced like any other.
+        ReplaceSelfTy {
+            self_ty: item_impl_template.self_ty.require_path()?,
+        }
+        .visit_impl_item_mut(&mut impl_item);
+
+        for mut ruleset_attr in ruleset_attrs {
+            let borrow_span = extract_borrow(&mut ruleset_attr)?;
+            let mut ruleset = parse_ruleset(ruleset_attr, &context.stateset)?;
+
+            // Rulesets that are valid on their own may conflict with the impl ruleset.
+            ruleset.merge(&impl_ruleset);
+            validate_ruleset(&ruleset, &context.stateset)?;
+
+            // Clone the impl block. Each function will go in its own block due to differing
+            // generics.
+            let mut item_impl = item_impl_template.clone();
+            let mut impl_item = impl_item.clone();
+            let associated_fn = impl_item.require_fn_mut()?;
+
+            if context.documentation.description {
+                associated_fn.attrs.push(describe_ruleset(&ruleset));
+            }
+
+            if !context.documentation.ugly {
+                let mut pretty_associated_fn = associated_fn.clone();
+
+                // Replace `_` in the return type with the designated parameters' idents.
+                context
+                    .type_infer_replacer(context.param_tys())
+                    .visit_return_type_mut(&mut pretty_associated_fn.sig.output);
+                pretty_associated_fn.block = parse_squote!({ unreachable!() });
+
+                pretty_item_impl
+                    .items
+                    .push(ImplItem::Fn(pretty_associated_fn));
+            }
+
+            let item_impl_path = item_impl.self_ty.require_path_mut()?;
+            let args = &mut item_impl_path
+                .last_mut()?
+                .arguments
+                .require_angle_bracketed_mut()?
+                .args;
+
+            validate_receiver(associated_fn, &ruleset, borrow_span)?;
+
+            if associated_fn.sig.receiver().is_some() {
+                // Splice in reverse, so the indices of earlier parameters stay valid.
+                for group in context.groups.iter().rev() {
+                    item_impl.generics.params.call(|params| {
+                        params.splice(
+                            group.param_index..(group.param_index + 1),
+                            states_generic_params(&context.stateset, &ruleset, group),
+                        );
+                    });
+
+                    // Replace the designated argument with the ingoing type.
+                    args[group.arg_index] =
+                        GenericArgument::Type(states_in_ty(&context.stateset, &ruleset, group));
+                }
+
+                let states_in_tys = context
+                    .groups
+                    .iter()
+                    .map(|group| states_in_ty(&context.stateset, &ruleset, group))
+                    .collect_vec();
+
+                let states_out_tys = context
+                    .groups
+                    .iter()
+                    .map(|group| states_out_ty(&context.stateset, &ruleset, group))
+                    .collect_vec();
+
+                add_expr_predicates(&mut item_impl.generics, &ruleset);
+                context.add_dependent_predicates(&mut item_impl.generics, &ruleset);
+                context.translate_param_predicates(
+                    &mut item_impl.generics,
+                    &states_in_tys,
+                    Some(&states_out_tys),
+                );
+
+                // Replace `_` in the return type with the outgoing types.
+                context
+                    .type_infer_replacer(states_out_tys)
+                    .visit_return_type_mut(&mut associated_fn.sig.output);
+
+                // Replace the designated parameters with the ingoing types, which are
+                // named in the signature and body.
+                context
+                    .type_param_replacer(states_in_tys)
+                    .visit_impl_item_fn_mut(associated_fn);
+
+                replace_expr_infer(associated_fn, &context.self_ident)?;
+            } else {
+                forbid_receiverless_assertions(&ruleset)?;
+
+                // Remove in reverse, so the indices of earlier parameters stay valid.
+                for group in context.groups.iter().rev() {
+                    // Replace the designated argument with the stateless type.
+                    args[group.arg_index] = parse_squote!(::stated::__);
+
+                    // Remove the designated parameter.
+                    item_impl
+                        .generics
+                        .params
+                        .call(|params| params.remove(group.param_index));
+                }
+
+                let states_constructed_tys = context
+                    .groups
+                    .iter()
+                    .map(|group| states_constructed_ty(&context.stateset, &ruleset, group))
+                    .collect_vec();
+
+                context.translate_param_predicates(
+                    &mut item_impl.generics,
+                    &states_constructed_tys,
+                    None,
+                );
+
+                // Replace `_` in the return type with the constructed types.
+                context
+                    .type_infer_replacer(states_constructed_tys.clone())
+                    .visit_return_type_mut(&mut associated_fn.sig.output);
+
+                // Replace the designated parameters with the constructed types, which
+                // are named in the signature and body.
+                context
+                    .type_param_replacer(states_constructed_tys)
+                    .visit_impl_item_fn_mut(associated_fn);
+            }
+
+            fill_phantom_field(
+                &mut associated_fn.block,
+                &item_impl_path.path,
+                context.variants.as_deref(),
+            );
+
+            item_impl.items.push(impl_item);
+
+            if context.documentation.ugly {
+                expansions.push(squote!(#item_impl));
+            } else {
+                expansions.push(squote! {
+                    #[cfg(not(doc))]
+                    #item_impl
+                });
+            }
+        }
+    }
+
+    if !context.documentation.ugly {
+        expansions.push(squote! {
             #[cfg(doc)]
             #pretty_item_impl
         });
@@ -518,3 +1211,2044 @@ pub fn expand_item_impl_internal(
         #(#expansions)*
     })
 }
+
+fn expand_item_impl_trait(
+    context: &ImplContext,
+    impl_ruleset: Stateset,
+    mut item_impl: ItemImpl,
+) -> Result<TokenStream2> {
+    // The rulesets of all trait methods are merged into the impl ruleset, since
+    // they must share the same impl block.
+    let mut merged_ruleset = impl_ruleset;
+
+    // The outgoing states are shared by every method and associated item, so
+    // only one method can transition states.
+    let mut transitioning = false;
+
+    for impl_item in item_impl.items.iter_mut() {
+        // Associated types and constants can't have rulesets.
+        let ImplItem::Fn(associated_fn) = impl_item else {
+            continue;
+        };
+
+        let mut ruleset_attrs = associated_fn
+            .attrs
+            .extract_if(.., |attr| attr.path().is_ident("stated"))
+            .collect_vec();
+
+        // Validate there is at most one ruleset.
+        if let Some(ruleset_attr) = ruleset_attrs.get(1) {
+            return Err(Error::new(
+                ruleset_attr.span(),
+                "trait methods cannot have more than one ruleset",
+            ));
+        }
+
+        // Trait methods without a ruleset are allowed.
+        let Some(mut ruleset_attr) = ruleset_attrs.pop() else {
+            continue;
+        };
+
+        let borrow_span = extract_borrow(&mut ruleset_attr)?;
+        let ruleset = parse_ruleset(ruleset_attr, &context.stateset)?;
+
+        validate_receiver(associated_fn, &ruleset, borrow_span)?;
+
+        if associated_fn.sig.receiver().is_none() {
+            forbid_receiverless_assertions(&ruleset)?;
+        }
+
+        if let Some(span) = find_transition(&ruleset) {
+            if transitioning {
+                return Err(Error::new(
+                    span,
+                    "only one method of a trait impl can transition states",
+                ));
+            }
+
+            transitioning = true;
+        }
+
+        if context.documentation.description {
+            associated_fn.attrs.push(describe_ruleset(&ruleset));
+        }
+
+        merged_ruleset.merge(&ruleset);
+    }
+
+    // Rulesets that are valid on their own may conflict once merged.
+    validate_ruleset(&merged_ruleset, &context.stateset)?;
+
+    let mut expansions = Vec::new();
+
+    if !context.documentation.ugly {
+        let mut pretty_item_impl = item_impl.clone();
+        context.add_states_trait_predicates(&mut pretty_item_impl.generics);
+
+        // Replace `_` in the signatures with the designated parameters' idents.
+        for impl_item in pretty_item_impl.items.iter_mut() {
+            replace_impl_item_type_infer(
+                impl_item,
+                context.type_infer_replacer(context.param_tys()).as_mut(),
+            );
+
+            if let ImplItem::Fn(associated_fn) = impl_item {
+                associated_fn.block = parse_squote!({ unreachable!() });
+            }
+        }
+
+        expansions.push(squote! {
+            #[cfg(doc)]
+            #pretty_item_impl
+        });
+    }
+
+    let item_impl_path = item_impl.self_ty.require_path_mut()?;
+    let args = &mut item_impl_path
+        .last_mut()?
+        .arguments
+        .require_angle_bracketed_mut()?
+        .args;
+
+    // Splice in reverse, so the indices of earlier parameters stay valid.
+    for group in context.groups.iter().rev() {
+        item_impl.generics.params.call(|params| {
+            params.splice(
+                group.param_index..(group.param_index + 1),
+                states_generic_params(&context.stateset, &merged_ruleset, group),
+            );
+        });
+
+        // Replace the designated argument with the ingoing type.
+        args[group.arg_index] =
+            GenericArgument::Type(states_in_ty(&context.stateset, &merged_ruleset, group));
+    }
+
+    let states_in_tys = context
+        .groups
+        .iter()
+        .map(|group| states_in_ty(&context.stateset, &merged_ruleset, group))
+        .collect_vec();
+
+    let states_out_tys = context
+        .groups
+        .iter()
+        .map(|group| states_out_ty(&context.stateset, &merged_ruleset, group))
+        .collect_vec();
+
+    add_expr_predicates(&mut item_impl.generics, &merged_ruleset);
+    context.add_dependent_predicates(&mut item_impl.generics, &merged_ruleset);
+    context.translate_param_predicates(
+        &mut item_impl.generics,
+        &states_in_tys,
+        Some(&states_out_tys),
+    );
+
+    for impl_item in item_impl.items.iter_mut() {
+        // Replace `_` in the signatures with the outgoing types.
+        replace_impl_item_type_infer(
+            impl_item,
+            context.type_infer_replacer(states_out_tys.clone()).as_mut(),
+        );
+
+        // Replace the designated parameters with the ingoing types.
+        context
+            .type_param_replacer(states_in_tys.clone())
+            .visit_impl_item_mut(impl_item);
+
+        let ImplItem::Fn(associated_fn) = impl_item else {
+            continue;
+        };
+
+        replace_expr_infer(associated_fn, &context.self_ident)?;
+
+        fill_phantom_field(
+            &mut associated_fn.block,
+            &item_impl_path.path,
+            context.variants.as_deref(),
+        );
+    }
+
+    if context.documentation.ugly {
+        expansions.push(squote!(#item_impl));
+    } else {
+        expansions.push(squote! {
+            #[cfg(not(doc))]
+            #item_impl
+        });
+    }
+
+    Ok(squote! {
+        #(#expansions)*
+    })
+}
+
+/// Validate the aliases in `stateset` only have declared states as members.
+fn validate_aliases(stateset: &Stateset) -> Result<()> {
+    if let Some(member) = stateset["alias"]
+        .iter()
+        .flat_map(|alias| &alias.members)
+        .find(|member| !stateset["states"].contains(member))
+    {
+        return Err(Error::new(
+            member.span(),
+            "alias members must be declared states",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Generate a type alias for each alias in `stateset`, naming the type with
+/// `ident` and `generics` that has the alias's members enabled. The other
+/// states are left as generic parameters.
+fn alias_types(
+    vis: &Visibility,
+    ident: &Ident,
+    generics: &Generics,
+    stateset: &Stateset,
+) -> Result<Vec<ItemType>> {
+    let designated_params = get_designated_params(&generics.params)?;
+
+    let mut alias_types = Vec::new();
+
+    for alias in stateset["alias"].iter() {
+        let mut alias_params: Vec<TokenStream2> = Vec::new();
+        let mut args: Vec<TokenStream2> = Vec::new();
+
+        for (param_index, param) in generics.params.iter().enumerate() {
+            if let Some(designated) = designated_params
+                .iter()
+                .find(|designated| designated.param_index == param_index)
+            {
+                let slots = declarations(stateset)
+                    .filter(|state| state.group == designated.group)
+                    .map(|state| {
+                        let carried_ident = state.ty.as_deref().and_then(param_ident);
+
+                        if let Some(carried_ident) = carried_ident
+                            && alias.members.contains(state)
+                        {
+                            // The carried type stays generic.
+                            alias_params.push(squote!(#carried_ident));
+                            squote!(::stated::With<#carried_ident>)
+                        } else if alias.members.contains(state) {
+                            squote!(::stated::Y)
+                        } else {
+                            alias_params.push(squote!(#{state.ident}));
+                            squote!(#{state.ident})
+                        }
+                    })
+                    .collect_vec();
+
+                args.push(squote!((#(#slots),*)));
+                continue;
+            }
+
+            // Bounds and defaults aren't enforced on type aliases, so they are left out.
+            match param {
+                GenericParam::Type(ty_param) => {
+                    alias_params.push(squote!(#{ty_param.ident}));
+                    args.push(squote!(#{ty_param.ident}));
+                }
+                GenericParam::Lifetime(lifetime_param) => {
+                    alias_params.push(squote!(#{lifetime_param.lifetime}));
+                    args.push(squote!(#{lifetime_param.lifetime}));
+                }
+                GenericParam::Const(const_param) => {
+                    alias_params.push(squote!(const #{const_param.ident}: #{const_param.ty}));
+                    args.push(squote!(#{const_param.ident}));
+                }
+            }
+        }
+
+        let alias_ident = format_ident!("{ident}{}", alias.ident);
+        let doc = format!(" [`{ident}`] with the states of `{}` enabled.", alias.ident);
+
+        alias_types.push(parse_squote! {
+            #[doc = #doc]
+            #vis type #alias_ident<#(#alias_params),*> = #ident<#(#args),*>;
+        });
+    }
+
+    Ok(alias_types)
+}
+
+/// Replace the types of the fields of `item_struct` that depend on a state with
+/// the type selected by the state, which is read through a hidden trait of the
+/// states type. Returns the traits, and the predicates that keep the states the
+/// fields depend on unchanged when reconstructed with `re_params`.
+fn replace_dependent_tys(
+    item_struct: &mut ItemStruct,
+    designated_params: &[Designated],
+    re_params: &[Ident],
+    stateset: &Stateset,
+) -> Result<(Vec<TokenStream2>, Vec<WherePredicate>)> {
+    // The states the fields depend on, ordered like the designated parameters.
+    let mut dependent_states = vec![Vec::new(); designated_params.len()];
+
+    for field in item_struct.fields.iter_mut() {
+        let field_attrs = FieldAttrs::from_field(field)?;
+        field.attrs.retain(|attr| !attr.path().is_ident("stated"));
+
+        let Some(dependent_ty) = field_attrs.dependent_ty else {
+            continue;
+        };
+
+        // Validate the written type is a placeholder, since it's replaced.
+        match &field.ty {
+            Type::Infer(_) => {}
+            Type::Tuple(tuple) if tuple.elems.is_empty() => {}
+            ty => {
+                return Err(Error::new(
+                    ty.span(),
+                    "state-dependent fields must have the placeholder type `_` or `()`",
+                ));
+            }
+        }
+
+        let state = dependent_ty.state;
+
+        // Validate the state is a declared state that is either enabled or disabled.
+        match stateset["states"]
+            .iter()
+            .find(|declared| **declared == state)
+        {
+            Some(declared) if declared.ty.is_some() => {
+                return Err(Error::new(
+                    state.span(),
+                    "field types cannot depend on parameterized states",
+                ));
+            }
+            Some(_) => {}
+            None => {
+                return Err(Error::new(
+                    state.span(),
+                    "field types can only depend on declared states",
+                ));
+            }
+        }
+
+        let Some(index) = designated_params
+            .iter()
+            .position(|designated| designated.group == state.group)
+        else {
+            return Err(Error::new(
+                state.span(),
+                "state does not belong to a designated group",
+            ));
+        };
+
+        let param_ident = &item_struct.generics.params[designated_params[index].param_index]
+            .require_type_param()?
+            .ident;
+        let trait_ident = states_trait_ident(&item_struct.ident, state.group.as_ref());
+
+        field.ty = parse_squote! {
+            <<#param_ident as #trait_ident>::#{state.ident} as ::stated::Bit>::Select<
+                #{dependent_ty.when_ty},
+                #{dependent_ty.otherwise_ty},
+            >
+        };
+
+        if !dependent_states[index].contains(&state.ident) {
+            dependent_states[index].push(state.ident);
+        }
+    }
+
+    let mut states_traits = Vec::new();
+    let mut re_predicates = Vec::new();
+
+    for ((designated, re_param), states) in designated_params
+        .iter()
+        .zip(re_params)
+        .zip(dependent_states)
+    {
+        if states.is_empty() {
+            continue;
+        }
+
+        let param_ident = item_struct.generics.params[designated.param_index]
+            .require_type_param()?
+            .ident
+            .clone();
+        let trait_ident = states_trait_ident(&item_struct.ident, designated.group.as_ref());
+        let slots = declarations(stateset)
+            .filter(|state| state.group == designated.group)
+            .map(|state| &state.ident)
+            .collect_vec();
+
+        // A single slot is the states type itself, which covers the stateless type.
+        // Otherwise, the stateless type is a disabled state for every slot.
+        let stateless_impl = (slots.len() > 1).then(|| {
+            squote! {
+                impl #trait_ident for ::stated::__ {
+                    #(type #states = ::stated::__;)*
+                }
+            }
+        });
+
+        states_traits.push(squote! {
+            #[doc(hidden)]
+            #[allow(non_camel_case_types)]
+            #{item_struct.vis} trait #trait_ident {
+                #(type #states: ::stated::Bit;)*
+            }
+
+            impl<#(#slots),*> #trait_ident for (#(#slots),*)
+            where
+                #(#states: ::stated::Bit,)*
+            {
+                #(type #states = #states;)*
+            }
+
+            #stateless_impl
+        });
+
+        item_struct
+            .generics
+            .make_where_clause()
+            .predicates
+            .push(parse_squote!(#param_ident: #trait_ident));
+
+        re_predicates.push(parse_squote! {
+            #re_param: #trait_ident<#(#states = <#param_ident as #trait_ident>::#states),*>
+        });
+    }
+
+    Ok((states_traits, re_predicates))
+}
+
+/// Generate an impl block for the accessors of the fields of `item_struct`,
+/// which is expanded with `metas` like any other impl block. Returns `None` if
+/// there are no accessors.
+fn accessor_impl(
+    metas: &Punctuated<Meta, Token![,]>,
+    item_struct: &ItemStruct,
+) -> Result<Option<TokenStream2>> {
+    let mut accessor_fns = Vec::new();
+
+    for field in item_struct.fields.iter() {
+        let field_attrs = FieldAttrs::from_field(field)?;
+
+        for accessor in field_attrs.accessors {
+            let Some(field_ident) = &field.ident else {
+                return Err(Error::new(
+                    accessor.span,
+                    "only named fields can have accessors",
+                ));
+            };
+
+            // The type of a field that depends on a state is only known if the accessor
+            // asserts or rejects the state.
+            let ty = match &field_attrs.dependent_ty {
+                Some(dependent_ty) => {
+                    let mut ruleset = Stateset::default().support("assert").support("reject");
+                    ruleset.extend_with_metas(&accessor.ruleset)?;
+
+                    if ruleset["assert"].contains(&dependent_ty.state) {
+                        &dependent_ty.when_ty
+                    } else if ruleset["reject"].contains(&dependent_ty.state) {
+                        &dependent_ty.otherwise_ty
+                    } else {
+                        return Err(Error::new(
+                            accessor.span,
+                            "accessors of dependent fields must assert or reject the state",
+                        ));
+                    }
+                }
+                None => &field.ty,
+            };
+
+            let ruleset = &accessor.ruleset;
+
+            accessor_fns.push(if accessor.mutable {
+                let fn_ident = format_ident!("{}_mut", field_ident.unraw(), span = accessor.span);
+                let doc = format!(" Get a mutable reference to `{field_ident}`.");
+
+                squote! {
+                    #[doc = #doc]
+                    #[stated(#ruleset)]
+                    #{item_struct.vis} fn #fn_ident(&mut self) -> &mut #ty {
+                        &mut self.#field_ident
+                    }
+                }
+            } else {
+                let mut fn_ident = field_ident.clone();
+                fn_ident.set_span(accessor.span);
+                let doc = format!(" Get a reference to `{field_ident}`.");
+
+                squote! {
+                    #[doc = #doc]
+                    #[stated(borrow, #ruleset)]
+                    #{item_struct.vis} fn #fn_ident(&self) -> &#ty {
+                        &self.#field_ident
+                    }
+                }
+            });
+        }
+    }
+
+    if accessor_fns.is_empty() {
+        return Ok(None);
+    }
+
+    // The designating attributes are kept, so the impl block is expanded like one
+    // written for the struct.
+    let (impl_generics, ty_generics, where_clause) = item_struct.generics.split_for_impl();
+
+    Ok(Some(squote! {
+        #[::stated::stated_internal(#metas)]
+        impl #impl_generics #{item_struct.ident} #ty_generics #where_clause {
+            #(#accessor_fns)*
+        }
+    }))
+}
+
+/// Get the ident of the hidden trait that reads the states of `group` from the
+/// states type of the type named `ident`.
+fn states_trait_ident(ident: &Ident, group: Option<&Ident>) -> Ident {
+    match group {
+        Some(group) => format_ident!("__{ident}States_{group}"),
+        None => format_ident!("__{ident}States"),
+    }
+}
+
+/// Remove the designating attributes from `generics`. Returns the type of the
+/// phantom field, the generic parameters of the reconstruct method, their
+/// predicates, and the generics of the reconstructed type.
+fn strip_designated_params(
+    generics: &mut Generics,
+) -> Result<(Type, Vec<Ident>, Vec<WherePredicate>, Generics)> {
+    let designated_params = get_designated_params(&generics.params)?;
+
+    let mut designated_param_idents = Vec::new();
+
+    for designated in designated_params.iter() {
+        let designated_param = generics.params[designated.param_index].require_type_param_mut()?;
+
+        // Remove the designating attribute from the designated parameter.
+        designated_param.attrs.remove(designated.attr_index);
+
+        designated_param_idents.push(designated_param.ident.clone());
+    }
+
+    // The generic parameters added to track states must be changed when
+    // reconstructed.
+    let re_params = match designated_params.len() {
+        1 => vec![format_ident!("__Re")],
+        len => (0..len).map(|index| format_ident!("__Re{index}")).collect(),
+    };
+
+    let mut re_generics = generics.clone();
+    let mut re_predicates: Vec<WherePredicate> = Vec::new();
+
+    for ((designated, re_param), designated_param_ident) in designated_params
+        .iter()
+        .zip(&re_params)
+        .zip(&designated_param_idents)
+    {
+        re_generics.params[designated.param_index] = parse_squote!(#re_param);
+
+        // The reconstructed states must satisfy the bounds of the designated
+        // parameter, including those in the where clause.
+        let bounds = &generics.params[designated.param_index]
+            .require_type_param()?
+            .bounds;
+
+        if !bounds.is_empty() {
+            re_predicates.push(parse_squote!(#re_param: #bounds));
+        }
+
+        for predicate in generics
+            .where_clause
+            .iter()
+            .flat_map(|where_clause| &where_clause.predicates)
+        {
+            if let WherePredicate::Type(PredicateType {
+                lifetimes: None,
+                bounded_ty,
+                bounds,
+                ..
+            }) = predicate
+                && bounded_ty.is_param(designated_param_ident)
+            {
+                re_predicates.push(parse_squote!(#re_param: #bounds));
+            }
+        }
+    }
+
+    // A single designated parameter is tracked as is. Multiple are tracked as a
+    // tuple.
+    let phantom_ty = match designated_param_idents.as_slice() {
+        [designated_param_ident] => {
+            parse_squote!(::std::marker::PhantomData<#designated_param_ident>)
+        }
+        designated_param_idents => {
+            parse_squote!(::std::marker::PhantomData<(#(#designated_param_idents),*)>)
+        }
+    };
+
+    Ok((phantom_ty, re_params, re_predicates, re_generics))
+}
+
+/// Replace `Initial` as the default of the designated parameters in `generics`
+/// with the states type produced by the preset states in `stateset`.
+fn replace_initial_defaults(generics: &mut Generics, stateset: &Stateset) -> Result<()> {
+    // Aliases and substates can be preset, so they are expanded like in impl
+    // blocks.
+    let mut stateset = stateset.clone();
+    let aliases = stateset["alias"].clone();
+    stateset.expand_aliases(&aliases);
+    let declared = stateset["states"].clone();
+    stateset.expand_substates(&declared);
+
+    for Designated {
+        param_index, group, ..
+    } in get_designated_params(&generics.params)?
+    {
+        let designated_param = generics.params[param_index].require_type_param_mut()?;
+
+        let Some(default) = &mut designated_param.default else {
+            continue;
+        };
+
+        if !default
+            .require_path()
+            .is_ok_and(|ty_path| ty_path.qself.is_none() && ty_path.path.is_ident("Initial"))
+        {
+            continue;
+        }
+
+        // The type is defined by its own parameters, so the argument matches the
+        // parameter.
+        let group = Group {
+            name: group,
+            param_index,
+            param_ident: designated_param.ident.clone(),
+            arg_index: param_index,
+        };
+
+        *default = states_constructed_ty(&stateset, &new_ruleset(), &group);
+    }
+
+    Ok(())
+}
+
+/// Add a phantom field of type `phantom_ty` to `fields`.
+fn add_phantom_field(fields: &mut Fields, phantom_ty: &Type) {
+    match fields {
+        Fields::Named(FieldsNamed { named, .. }) => {
+            named.push(parse_squote!(__states: #phantom_ty));
+        }
+        Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => {
+            unnamed.push(parse_squote!(#phantom_ty));
+        }
+        Fields::Unit => {
+            // Unit structs and variants are changed to tuple structs and variants.
+            *fields = Fields::Unnamed(parse_squote!((#phantom_ty)));
+        }
+    }
+}
+
+/// Fill in the phantom field wherever the struct at `path` is constructed or
+/// matched in `block`. If the struct is an enum, its `variants` are filled in
+/// instead.
+fn fill_phantom_field(block: &mut Block, path: &Path, variants: Option<&[Ident]>) {
+    // `Self` is the ingoing type, so constructions name the type instead to infer
+    // the outgoing type.
+    ReplaceSelfInStructConstruction { path, variants }.visit_block_mut(block);
+
+    let paths = match variants {
+        Some(variants) => variants
+            .iter()
+            .map(|variant| parse_squote!(#path::#variant))
+            .collect_vec(),
+        None => vec![path.clone()],
+    };
+
+    for path in paths {
+        AddFieldInStructConstruction {
+            path: &path,
+            field_member: parse_squote!(__states),
+            field_expr: parse_squote!(::std::marker::PhantomData),
+        }
+        .visit_block_mut(block);
+
+        AddFieldInStructPattern { path: &path }.visit_block_mut(block);
+    }
+}
+
+/// Validate the receiver of `associated_fn` can satisfy `ruleset`, and that it's
+/// `&self` if the method is borrowed, as marked by `borrow_span`.
+fn validate_receiver(
+    associated_fn: &ImplItemFn,
+    ruleset: &Stateset,
+    borrow_span: Option<Span>,
+) -> Result<()> {
+    let receiver = associated_fn.sig.receiver();
+
+    // Validate borrowed methods are read-only, which means they take `&self`.
+    if let Some(borrow_span) = borrow_span
+        && !receiver.is_some_and(|receiver| {
+            matches!(
+                receiver.ty.as_ref(),
+                Type::Reference(TypeReference {
+                    mutability: None,
+                    ..
+                })
+            )
+        })
+    {
+        return Err(Error::new(
+            borrow_span,
+            "borrowed methods must take `&self`",
+        ));
+    }
+
+    // A reference receiver can't be reconstructed, so its states can't change.
+    if let Some(receiver) = receiver
+        && let Type::Reference(_) = receiver.ty.as_ref()
+        && let Some(span) = find_transition(ruleset)
+    {
+        return Err(Error::new(
+            span,
+            "methods with a reference receiver can only assert or reject states",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Remove `borrow` from `ruleset_attr`. Returns its span, if it was there.
+fn extract_borrow(ruleset_attr: &mut Attribute) -> Result<Option<Span>> {
+    let Meta::List(meta_list) = &mut ruleset_attr.meta else {
+        return Ok(None);
+    };
+
+    let metas = meta_list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+    let (borrows, metas): (Vec<_>, Vec<_>) = metas
+        .into_iter()
+        .partition(|meta| meta.path().is_ident("borrow"));
+
+    if let Some(borrow) = borrows.get(1) {
+        return Err(Error::new(borrow.span(), "redundant `borrow` attribute"));
+    }
+
+    let Some(borrow) = borrows.first() else {
+        return Ok(None);
+    };
+
+    borrow.require_path_only()?;
+    meta_list.tokens = squote!(#(#metas),*);
+
+    Ok(Some(borrow.span()))
+}
+
+/// Find the span of the first rule in `ruleset` that transitions states.
+fn find_transition(ruleset: &Stateset) -> Option<Span> {
+    ruleset["assign"]
+        .first()
+        .or_else(|| ruleset["delete"].first())
+        .or_else(|| ruleset["increment"].first())
+        .or_else(|| ruleset["toggle"].first())
+        .or_else(|| ruleset["copy"].first())
+        .or_else(|| ruleset["advance"].first())
+        .map(State::span)
+        .or_else(|| {
+            ruleset
+                .conditionals("when")
+                .first()
+                .map(|conditional| conditional.span)
+        })
+}
+
+/// Create an empty ruleset.
+fn new_ruleset() -> Stateset {
+    Stateset::default()
+        .support("assert")
+        .support("reject")
+        .support("assign")
+        .support("delete")
+        .support("increment")
+        .support("toggle")
+        .support("copy")
+        .support("advance")
+        .support("at_least")
+        .support("when")
+}
+
+/// Parse the ruleset in `ruleset_attr` and validate it against the declared
+/// states in `stateset`.
+fn parse_ruleset(ruleset_attr: Attribute, stateset: &Stateset) -> Result<Stateset> {
+    let mut ruleset = new_ruleset();
+
+    // Validate the ruleset attribute is not a name-value.
+    ruleset_attr.meta.forbid_name_value()?;
+
+    if let Meta::List(MetaList { tokens, .. }) = ruleset_attr.meta {
+        let metas = Punctuated::<Meta, Token![,]>::parse_terminated.parse2(tokens)?;
+
+        // Validate all attributes in the metas are supported.
+        if let Some(meta) = metas
+            .iter()
+            .filter(|meta| !meta.path().is_ident("assert"))
+            .filter(|meta| !meta.path().is_ident("reject"))
+            .filter(|meta| !meta.path().is_ident("assign"))
+            .filter(|meta| !meta.path().is_ident("delete"))
+            .filter(|meta| !meta.path().is_ident("increment"))
+            .filter(|meta| !meta.path().is_ident("toggle"))
+            .filter(|meta| !meta.path().is_ident("copy"))
+            .filter(|meta| !meta.path().is_ident("advance"))
+            .find(|meta| !meta.path().is_ident("when"))
+        {
+            return Err(Error::new(meta.path().span(), "invalid attribute"));
+        }
+
+        ruleset.extend_with_metas(&metas)?;
+    }
+
+    ruleset.expand_aliases(&stateset["alias"]);
+    ruleset.expand_substates(&stateset["states"]);
+
+    validate_ruleset(&ruleset, stateset)?;
+
+    Ok(ruleset)
+}
+
+/// Validate `ruleset` against the declared states in `stateset`.
+fn validate_ruleset(ruleset: &Stateset, stateset: &Stateset) -> Result<()> {
+    // Validate expressions are only asserted or rejected, since they can't be
+    // transitioned.
+    if let Some(expr) = ["assign", "delete", "increment", "toggle", "advance"]
+        .into_iter()
+        .find_map(|kind| ruleset.exprs(kind).first())
+        .or_else(|| {
+            ruleset.conditionals("when").iter().find_map(|conditional| {
+                ["assign", "delete"]
+                    .into_iter()
+                    .find_map(|kind| conditional.effects.exprs(kind).first())
+            })
+        })
+    {
+        return Err(Error::new(
+            expr.span(),
+            "expressions can only be asserted or rejected",
+        ));
+    }
+
+    validate_parameterized(ruleset, stateset)?;
+
+    // Validate expressions and conditions only use declared states.
+    if let Some(state) = ruleset
+        .exprs("assert")
+        .iter()
+        .chain(ruleset.exprs("reject"))
+        .chain(
+            ruleset
+                .conditionals("when")
+                .iter()
+                .map(|conditional| &conditional.condition),
+        )
+        .flat_map(StateExpr::states)
+        .find(|state| !stateset["states"].contains(state))
+    {
+        return Err(Error::new(
+            state.span(),
+            "expressions can only use declared states",
+        ));
+    }
+
+    // Validate exclusive groups are only asserted or assigned, since exactly one
+    // of their choices is always enabled.
+    if let Some(state) = ruleset["reject"]
+        .iter()
+        .chain(&ruleset["delete"])
+        .find(|state| !state.choices.is_empty())
+    {
+        return Err(Error::new(
+            state.span(),
+            "exclusive groups can only be asserted or assigned",
+        ));
+    }
+
+    // Validate at most one choice of an exclusive group is selected.
+    if let Some(choice) = ruleset["assert"]
+        .iter()
+        .chain(&ruleset["assign"])
+        .find_map(|state| state.choices.get(1))
+    {
+        return Err(Error::new(choice.span(), "only one choice can be selected"));
+    }
+
+    // Validate counters are only bounded when asserted.
+    if let Some(bound) = ruleset["reject"]
+        .iter()
+        .chain(&ruleset["assign"])
+        .chain(&ruleset["delete"])
+        .chain(&ruleset["increment"])
+        .chain(&ruleset["toggle"])
+        .chain(&ruleset["advance"])
+        .find_map(|state| state.bound.as_ref())
+    {
+        return Err(Error::new(
+            bound.span,
+            "counters can only be bounded when asserted",
+        ));
+    }
+
+    // Validate the asserted states contain no duplicates. A counter can be
+    // asserted once per comparison.
+    if let Some(state) = ruleset["assert"]
+        .iter()
+        .duplicates_by(|state| {
+            let comparison = state.bound.as_ref().map(|bound| bound.comparison);
+            (&state.group, &state.ident, comparison)
+        })
+        .next()
+    {
+        return Err(Error::new(state.span(), "state is already asserted"));
+    }
+
+    // Validate the rejected states contain no duplicates.
+    if let Some(state) = ruleset["reject"]
+        .iter()
+        .duplicates_by(|state| (&state.group, &state.ident))
+        .next()
+    {
+        return Err(Error::new(state.span(), "state is already rejected"));
+    }
+
+    // Validate the assigned states contain no duplicates.
+    if let Some(state) = ruleset["assign"]
+        .iter()
+        .duplicates_by(|state| (&state.group, &state.ident))
+        .next()
+    {
+        return Err(Error::new(state.span(), "state is already assigned"));
+    }
+
+    // Validate the deleted states contain no duplicates.
+    if let Some(state) = ruleset["delete"]
+        .iter()
+        .duplicates_by(|state| (&state.group, &state.ident))
+        .next()
+    {
+        return Err(Error::new(state.span(), "state is already deleted"));
+    }
+
+    // Validate the incremented states contain no duplicates.
+    if let Some(state) = ruleset["increment"].iter().duplicates().next() {
+        return Err(Error::new(state.span(), "state is already incremented"));
+    }
+
+    // Validate the incremented states are declared counters.
+    if let Some(state) = ruleset["increment"].iter().find(|state| {
+        !state.choices.is_empty()
+            || !stateset["counter"]
+                .iter()
+                .any(|counter| counter.is_same(state))
+    }) {
+        return Err(Error::new(
+            state.span(),
+            "incremented state is not a declared counter",
+        ));
+    }
+
+    // Validate the toggled states contain no duplicates.
+    if let Some(state) = ruleset["toggle"]
+        .iter()
+        .duplicates_by(|state| (&state.group, &state.ident))
+        .next()
+    {
+        return Err(Error::new(state.span(), "state is already toggled"));
+    }
+
+    // Validate the copied states contain no duplicates.
+    if let Some(state) = ruleset["copy"]
+        .iter()
+        .duplicates_by(|state| (&state.group, &state.ident))
+        .next()
+    {
+        return Err(Error::new(state.span(), "state is already copied"));
+    }
+
+    // Validate the toggled states are declared states. Exclusive groups and
+    // counters aren't bits, so they can't be toggled.
+    if let Some(state) = ruleset["toggle"]
+        .iter()
+        .find(|state| !stateset["states"].contains(state))
+    {
+        return Err(Error::new(
+            state.span(),
+            "toggled state is not a declared state",
+        ));
+    }
+
+    // Validate substates aren't toggled, since they can't be enabled without their
+    // parent.
+    if let Some(state) = ruleset["toggle"].iter().find(|state| {
+        find_state(&stateset["states"], state).is_some_and(|state| state.parent.is_some())
+    }) {
+        return Err(Error::new(state.span(), "substates cannot be toggled"));
+    }
+
+    // Validate nested states aren't copied, since the copy can't account for the
+    // parent or substates.
+    if let Some(state) = ruleset["copy"].iter().find(|state| {
+        stateset["states"].iter().any(|declared| {
+            (declared.is_same(state) && declared.parent.is_some())
+                || (declared.group == state.group && declared.parent.as_ref() == Some(&state.ident))
+        })
+    }) {
+        return Err(Error::new(state.span(), "nested states cannot be copied"));
+    }
+
+    // Validate the copied states, and the states they copy, are declared states.
+    if let Some(state) = ruleset["copy"]
+        .iter()
+        .flat_map(|copy| [copy].into_iter().chain(&copy.members))
+        .find(|state| find_state(&stateset["states"], state).is_none())
+    {
+        return Err(Error::new(
+            state.span(),
+            "copied state is not a declared state",
+        ));
+    }
+
+    // Validate the toggled and copied states aren't also transitioned otherwise,
+    // since a state can only have one outgoing type.
+    if let Some(state) = ruleset["toggle"]
+        .iter()
+        .chain(&ruleset["copy"])
+        .find(|state| {
+            find_state(&ruleset["assign"], state).is_some()
+                || find_state(&ruleset["delete"], state).is_some()
+        })
+        .or_else(|| {
+            ruleset["copy"]
+                .iter()
+                .find(|state| find_state(&ruleset["toggle"], state).is_some())
+        })
+    {
+        return Err(Error::new(state.span(), "state is already transitioned"));
+    }
+
+    // Validate phases are only asserted or advanced, since they only move forward.
+    if let Some(state) = ruleset["reject"]
+        .iter()
+        .chain(&ruleset["assign"])
+        .chain(&ruleset["delete"])
+        .find(|state| find_phases(state, stateset).is_some())
+    {
+        return Err(Error::new(
+            state.span(),
+            "phases can only be asserted or advanced",
+        ));
+    }
+
+    // Validate the advanced states are declared phases.
+    if let Some(state) = ruleset["advance"]
+        .iter()
+        .chain(&ruleset["at_least"])
+        .find(|state| find_phases(state, stateset).is_none())
+    {
+        return Err(Error::new(state.span(), "state is not a declared phase"));
+    }
+
+    // Validate at most one phase is advanced to.
+    if let Some(state) = ruleset["advance"].get(1) {
+        return Err(Error::new(state.span(), "phase is already advanced"));
+    }
+
+    // Validate at most one phase is asserted, either exactly or as a lower bound.
+    if let Some(state) = ruleset["assert"]
+        .iter()
+        .filter(|state| find_phases(state, stateset).is_some())
+        .chain(&ruleset["at_least"])
+        .nth(1)
+    {
+        return Err(Error::new(state.span(), "phase is already asserted"));
+    }
+
+    // Validate the asserted states are declared.
+    if let Some(state) = ruleset["assert"]
+        .iter()
+        .find(|state| !is_declared(state, stateset))
+    {
+        return Err(Error::new(state.span(), "asserted state is not declared"));
+    }
+
+    // Validate the rejected states are declared.
+    if let Some(state) = ruleset["reject"]
+        .iter()
+        .find(|state| !is_declared(state, stateset))
+    {
+        return Err(Error::new(state.span(), "rejected state is not declared"));
+    }
+
+    // Validate the asserted states are declared.
+    if let Some(state) = ruleset["assign"]
+        .iter()
+        .find(|state| !is_declared(state, stateset))
+    {
+        return Err(Error::new(state.span(), "assigned state is not declared"));
+    }
+
+    // Validate the asserted states are declared.
+    if let Some(state) = ruleset["delete"]
+        .iter()
+        .find(|state| !is_declared(state, stateset))
+    {
+        return Err(Error::new(state.span(), "deleted state is not declared"));
+    }
+
+    // Validate the asserted and rejected states are disjoint.
+    if let Some(state) = ruleset["reject"]
+        .iter()
+        .find(|state| ruleset["assert"].contains(state))
+    {
+        return Err(Error::new(
+            state.span(),
+            "rejected state cannot also be asserted",
+        ));
+    }
+
+    // Validate the assigned and deleted states are disjoint.
+    if let Some(state) = ruleset["delete"]
+        .iter()
+        .find(|state| ruleset["assign"].contains(state))
+    {
+        return Err(Error::new(
+            state.span(),
+            "deleted state cannot also be assigned",
+        ));
+    }
+
+    // Validate the asserted and assigned states are disjoint.
+    if let Some(state) = ruleset["assign"]
+        .iter()
+        .find(|state| ruleset["assert"].contains(state))
+    {
+        // TODO(blocked): Emit a warning once procedural macro diagnostics is
+        // stabilized. Tracking issue: https://github.com/rust-lang/rust/issues/54140.
+        return Err(Error::new(
+            state.span(),
+            "asserted state doesn't need to be assigned",
+        ));
+    }
+
+    // Validate the rejected and deleted states are disjoint.
+    if let Some(state) = ruleset["delete"]
+        .iter()
+        .find(|state| ruleset["reject"].contains(state))
+    {
+        // TODO(blocked): Emit a warning once procedural macro diagnostics is
+        // stabilized. Tracking issue: https://github.com/rust-lang/rust/issues/54140.
+        return Err(Error::new(
+            state.span(),
+            "rejected state doesn't need to be deleted",
+        ));
+    }
+
+    // Validate the rules on each counter can be satisfied.
+    for counter in stateset["counter"].iter() {
+        CounterRules::new(counter, ruleset).validate()?;
+    }
+
+    // Validate the rules on the phases can be satisfied.
+    for phases in stateset["phases"].iter() {
+        PhaseRules::new(phases, ruleset).validate()?;
+    }
+
+    for conditional in ruleset.conditionals("when") {
+        validate_conditional(conditional, ruleset, stateset)?;
+    }
+
+    Ok(())
+}
+
+/// Validate the parameterized states in `ruleset` against the declared states
+/// in `stateset`.
+fn validate_parameterized(ruleset: &Stateset, stateset: &Stateset) -> Result<()> {
+    // Validate types are only carried by asserted and assigned states, since the
+    // other rules don't depend on the carried type.
+    if let Some(state) = ruleset
+        .iter()
+        .filter(|(kind, _)| *kind != "assert" && *kind != "assign")
+        .flat_map(|(_, states)| states)
+        .find(|state| state.ty.is_some())
+    {
+        return Err(Error::new(
+            state.span(),
+            "only asserted and assigned states can carry a type",
+        ));
+    }
+
+    // Validate types are only carried by parameterized states.
+    if let Some(state) = ruleset["assert"]
+        .iter()
+        .chain(&ruleset["assign"])
+        .find(|state| state.ty.is_some() && find_parameterized(state, stateset).is_none())
+    {
+        return Err(Error::new(
+            state.span(),
+            "only parameterized states can carry a type",
+        ));
+    }
+
+    // Validate assigned parameterized states carry a type.
+    if let Some(state) = ruleset["assign"]
+        .iter()
+        .find(|state| carried_ty(state).is_none() && find_parameterized(state, stateset).is_some())
+    {
+        return Err(Error::new(
+            state.span(),
+            "expected a type for the parameterized state",
+        ));
+    }
+
+    // Validate parameterized states aren't toggled or copied, since they aren't
+    // bits.
+    if let Some(state) = ruleset["toggle"]
+        .iter()
+        .chain(
+            ruleset["copy"]
+                .iter()
+                .flat_map(|copy| [copy].into_iter().chain(&copy.members)),
+        )
+        .find(|state| find_parameterized(state, stateset).is_some())
+    {
+        return Err(Error::new(
+            state.span(),
+            "parameterized states cannot be toggled or copied",
+        ));
+    }
+
+    // Validate parameterized states aren't used in expressions, conditions, or
+    // conditional effects, since they aren't bits.
+    if let Some(state) = ruleset
+        .exprs("assert")
+        .iter()
+        .chain(ruleset.exprs("reject"))
+        .chain(
+            ruleset
+                .conditionals("when")
+                .iter()
+                .map(|conditional| &conditional.condition),
+        )
+        .flat_map(StateExpr::states)
+        .chain(ruleset.conditionals("when").iter().flat_map(|conditional| {
+            conditional.effects["assign"]
+                .iter()
+                .chain(&conditional.effects["delete"])
+        }))
+        .find(|state| find_parameterized(state, stateset).is_some())
+    {
+        return Err(Error::new(
+            state.span(),
+            "parameterized states can only be asserted, rejected, assigned, or deleted",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validate the effects of `conditional` against `ruleset` and the declared
+/// states in `stateset`.
+fn validate_conditional(
+    conditional: &Conditional,
+    ruleset: &Stateset,
+    stateset: &Stateset,
+) -> Result<()> {
+    let effects = &conditional.effects;
+
+    // Validate the effects only transition declared states. Exclusive groups and
+    // counters aren't supported, since they have no single state to select.
+    if let Some(state) = effects["assign"]
+        .iter()
+        .chain(&effects["delete"])
+        .find(|state| !stateset["states"].contains(state))
+    {
+        return Err(Error::new(
+            state.span(),
+            "conditional effects can only transition declared states",
+        ));
+    }
+
+    // Validate the effects contain no duplicates.
+    if let Some(state) = effects["assign"]
+        .iter()
+        .chain(&effects["delete"])
+        .duplicates()
+        .next()
+    {
+        return Err(Error::new(state.span(), "state is already transitioned"));
+    }
+
+    // Validate the effects don't conflict with the unconditional transitions,
+    // which would always override them.
+    if let Some(state) = effects["assign"]
+        .iter()
+        .chain(&effects["delete"])
+        .find(|state| {
+            ["assign", "delete", "toggle", "copy"]
+                .into_iter()
+                .any(|kind| find_state(&ruleset[kind], state).is_some())
+        })
+    {
+        return Err(Error::new(
+            state.span(),
+            "state is already transitioned unconditionally",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Returns an error if `ruleset`, which belongs to an associated function
+/// without a receiver, asserts or rejects states, or computes transitions from
+/// the ingoing states.
+fn forbid_receiverless_assertions(ruleset: &Stateset) -> Result<()> {
+    // Conditions are evaluated with the ingoing states, which don't exist without
+    // a receiver.
+    if let Some(conditional) = ruleset.conditionals("when").first() {
+        return Err(Error::new(
+            conditional.span,
+            "only methods can have conditional effects",
+        ));
+    }
+
+    // Toggled and copied states are computed from the ingoing states too.
+    if let Some(state) = ruleset["toggle"]
+        .first()
+        .or_else(|| ruleset["copy"].first())
+    {
+        return Err(Error::new(
+            state.span(),
+            "only methods can toggle or copy states",
+        ));
+    }
+
+    if let Some(span) = ruleset["assert"]
+        .first()
+        .or_else(|| ruleset["reject"].first())
+        .or_else(|| ruleset["at_least"].first())
+        .map(State::span)
+        .or_else(|| ruleset.exprs("assert").first().map(StateExpr::span))
+        .or_else(|| ruleset.exprs("reject").first().map(StateExpr::span))
+    {
+        return Err(Error::new(span, "only methods can assert or reject states"));
+    }
+
+    Ok(())
+}
+
+/// Generate the description of `ruleset`.
+fn describe_ruleset(ruleset: &Stateset) -> Attribute {
+    Description::new(ruleset)
+        .line(DescriptionLine::new("assert").label("Assert"))
+        .line(DescriptionLine::new("at_least").label("Assert at least"))
+        .line(DescriptionLine::new("reject").label("Reject"))
+        .line(DescriptionLine::new("assign").label("Assign"))
+        .line(DescriptionLine::new("delete").label("Delete"))
+        .line(DescriptionLine::new("increment").label("Increment"))
+        .line(DescriptionLine::new("toggle").label("Toggle"))
+        .line(DescriptionLine::new("copy").label("Copy"))
+        .line(DescriptionLine::new("advance").label("Advance"))
+        .line(DescriptionLine::new("when").label("When"))
+        .generate()
+}
+
+/// Get all declarations in `stateset`, which are the states, exclusive groups,
+/// counters, and phases.
+fn declarations(stateset: &Stateset) -> impl Iterator<Item = &State> {
+    stateset["states"]
+        .iter()
+        .chain(&stateset["group"])
+        .chain(&stateset["counter"])
+        .chain(&stateset["phases"])
+}
+
+/// Check if `state` is declared in `stateset`, either as a state, as a choice
+/// of an exclusive group, as a bound on a counter, or as a phase.
+fn is_declared(state: &State, stateset: &Stateset) -> bool {
+    if !state.choices.is_empty() {
+        stateset["group"]
+            .iter()
+            .any(|group| state.choice_index(group).is_some())
+    } else if state.bound.is_some() {
+        stateset["counter"]
+            .iter()
+            .any(|counter| counter.is_same(state))
+    } else {
+        stateset["states"].contains(state) || find_phases(state, stateset).is_some()
+    }
+}
+
+/// Find the parameterized state declared in `stateset` that `state` refers to.
+fn find_parameterized<'a>(state: &State, stateset: &'a Stateset) -> Option<&'a State> {
+    find_state(&stateset["states"], state).filter(|state| state.ty.is_some())
+}
+
+/// Get the type carried by `state`, unless it is inferred.
+fn carried_ty(state: &State) -> Option<&Type> {
+    state
+        .ty
+        .as_deref()
+        .filter(|ty| !matches!(ty, Type::Infer(_)))
+}
+
+/// Get the type parameter `ty` of a parameterized state declaration.
+fn param_ident(ty: &Type) -> Option<&Ident> {
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => type_path.path.get_ident(),
+        _ => None,
+    }
+}
+
+/// Find the phases in `stateset` that `state` is a phase of.
+fn find_phases<'a>(state: &State, stateset: &'a Stateset) -> Option<&'a State> {
+    stateset["phases"]
+        .iter()
+        .find(|phases| state.phase_index(phases).is_some())
+}
+
+/// Find the state in `states` that sets the state `slot`.
+fn find_state<'a>(states: &'a [State], slot: &State) -> Option<&'a State> {
+    states.iter().find(|state| state.is_same(slot))
+}
+
+/// Get the type of the exclusive group `slot` when set by `state`.
+fn choice_ty(slot: &State, state: &State) -> Type {
+    let index = Literal::usize_unsuffixed(state.choice_index(slot).unwrap_or_default());
+    parse_squote!(::stated::Choice<#index>)
+}
+
+/// Get the type of the natural number `base + count`.
+fn succ_ty(count: usize, base: Type) -> Type {
+    (0..count).fold(base, |ty, _| parse_squote!(::stated::Succ<#ty>))
+}
+
+/// The rules in a ruleset that apply to a counter.
+struct CounterRules<'a> {
+    counter: &'a State,
+    /// The max of the counter, if it has one.
+    max: Option<usize>,
+    /// The lowest count allowed.
+    min_count: usize,
+    /// The highest count allowed, if bounded.
+    max_count: Option<usize>,
+    /// The span of the bound that last narrowed the allowed counts.
+    span: Span,
+    increment: bool,
+}
+
+impl<'a> CounterRules<'a> {
+    /// Collect the rules in `ruleset` that apply to `counter`.
+    fn new(counter: &'a State, ruleset: &Stateset) -> Self {
+        let max = counter.bound.as_ref().map(|bound| bound.value);
+
+        let mut rules = Self {
+            counter,
+            max,
+            min_count: 0,
+            max_count: max,
+            span: counter.span(),
+            increment: ruleset["increment"]
+                .iter()
+                .any(|state| state.is_same(counter)),
+        };
+
+        let bounds = ruleset["assert"]
+            .iter()
+            .filter(|state| state.is_same(counter))
+            .filter_map(|state| state.bound.as_ref());
+
+        for bound in bounds {
+            let (min_count, max_count) = match bound.comparison {
+                Comparison::Eq => (bound.value, Some(bound.value)),
+                Comparison::Ge => (bound.value, None),
+                Comparison::Gt => (bound.value + 1, None),
+                Comparison::Le => (0, Some(bound.value)),
+                // Nothing is less than zero, so the bounds can't be satisfied.
+                Comparison::Lt => match bound.value.checked_sub(1) {
+                    Some(max_count) => (0, Some(max_count)),
+                    None => (1, Some(0)),
+                },
+            };
+
+            rules.min_count = rules.min_count.max(min_count);
+            rules.max_count = match (rules.max_count, max_count) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+            rules.span = bound.span;
+        }
+
+        rules
+    }
+
+    /// Validate the rules can be satisfied.
+    fn validate(&self) -> Result<()> {
+        if self
+            .max_count
+            .is_some_and(|max_count| max_count < self.min_count)
+        {
+            return Err(Error::new(self.span, "counter bounds cannot be satisfied"));
+        }
+
+        match self.max {
+            Some(max) if self.increment && self.min_count >= max => Err(Error::new(
+                self.span,
+                "counter cannot be incremented past its max",
+            )),
+            // Without a max, only the count is tracked, so it can only be bounded above
+            // by an exact count.
+            None if self.max_count.is_some() && !self.is_exact() => Err(Error::new(
+                self.span,
+                "only counters with a max can be bounded above",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check if the rules allow exactly one count.
+    fn is_exact(&self) -> bool {
+        self.max_count == Some(self.min_count)
+    }
+
+    /// Get the lowest number of increments left that is allowed.
+    fn min_left(&self, max: usize) -> usize {
+        let max_count = self.max_count.unwrap_or(max).min(max);
+        let min_left = max - max_count;
+
+        // Incrementing requires an increment to be left.
+        if self.increment {
+            min_left.max(1)
+        } else {
+            min_left
+        }
+    }
+
+    /// Get the ident of the generic parameter for the increments left.
+    fn left_ident(&self) -> Ident {
+        format_ident!("__{}", self.counter.ident)
+    }
+
+    /// Get the generic parameters for the counter. Exact counts don't need any.
+    fn generic_params(&self) -> Vec<GenericParam> {
+        if self.is_exact() {
+            return Vec::new();
+        }
+
+        let count_ident = &self.counter.ident;
+
+        match self.max {
+            Some(_) => vec![
+                parse_squote!(#count_ident),
+                parse_squote!(#{self.left_ident()}),
+            ],
+            None => vec![parse_squote!(#count_ident)],
+        }
+    }
+
+    /// Get the type of the counter, with `increments` applied to the lowest
+    /// allowed count.
+    fn ty(&self, increments: usize) -> Type {
+        let (count_base, left_base): (Type, Type) = if self.is_exact() {
+            (parse_squote!(::stated::Zero), parse_squote!(::stated::Zero))
+        } else {
+            (
+                parse_squote!(#{self.counter.ident}),
+                parse_squote!(#{self.left_ident()}),
+            )
+        };
+
+        let count_ty = succ_ty(self.min_count + increments, count_base);
+
+        match self.max {
+            Some(max) => {
+                let left_ty = succ_ty(self.min_left(max) - increments, left_base);
+                parse_squote!((#count_ty, #left_ty))
+            }
+            None => count_ty,
+        }
+    }
+
+    /// Get the ingoing type of the counter.
+    fn in_ty(&self) -> Type {
+        self.ty(0)
+    }
+
+    /// Get the outgoing type of the counter.
+    fn out_ty(&self) -> Type {
+        self.ty(usize::from(self.increment))
+    }
+
+    /// Get the type of the counter when constructed, which starts at zero.
+    fn constructed_ty(&self) -> Type {
+        let increments = usize::from(self.increment);
+        let count_ty = succ_ty(increments, parse_squote!(::stated::Zero));
+
+        match self.max {
+            Some(max) => {
+                let left_ty = succ_ty(max - increments, parse_squote!(::stated::Zero));
+                parse_squote!((#count_ty, #left_ty))
+            }
+            None => count_ty,
+        }
+    }
+}
+
+/// The rules in a ruleset that apply to the phases.
+struct PhaseRules<'a> {
+    phases: &'a State,
+    /// The lowest phase allowed.
+    min_phase: usize,
+    /// The phase asserted exactly, if any.
+    exact: Option<usize>,
+    /// The phase advanced to, if any, and the span of its rule.
+    advance: Option<(usize, Span)>,
+}
+
+impl<'a> PhaseRules<'a> {
+    /// Collect the rules in `ruleset` that apply to `phases`.
+    fn new(phases: &'a State, ruleset: &Stateset) -> Self {
+        let exact = ruleset["assert"]
+            .iter()
+            .find_map(|state| state.phase_index(phases));
+        let at_least = ruleset["at_least"]
+            .iter()
+            .find_map(|state| state.phase_index(phases));
+
+        Self {
+            phases,
+            min_phase: exact.or(at_least).unwrap_or_default(),
+            exact,
+            advance: ruleset["advance"]
+                .iter()
+                .find_map(|state| Some((state.phase_index(phases)?, state.span()))),
+        }
+    }
+
+    /// Validate the rules can be satisfied.
+    fn validate(&self) -> Result<()> {
+        match self.advance {
+            Some((advance, span)) if advance < self.min_phase => Err(Error::new(
+                span,
+                "phases cannot be advanced to an earlier phase",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Get the index of the last phase.
+    fn last(&self) -> usize {
+        self.phases.choices.len() - 1
+    }
+
+    /// Get the ident of the generic parameter for the phases left.
+    fn left_ident(&self) -> Ident {
+        format_ident!("__{}", self.phases.ident)
+    }
+
+    /// Get the generic parameters for the phases. Exact phases don't need any.
+    fn generic_params(&self) -> Vec<GenericParam> {
+        if self.exact.is_some() {
+            return Vec::new();
+        }
+
+        vec![
+            parse_squote!(#{self.phases.ident}),
+            parse_squote!(#{self.left_ident()}),
+        ]
+    }
+
+    /// Get the type of the phases when at exactly `phase`.
+    fn exact_ty(&self, phase: usize) -> Type {
+        let phase_ty = succ_ty(phase, parse_squote!(::stated::Zero));
+        let left_ty = succ_ty(self.last() - phase, parse_squote!(::stated::Zero));
+        parse_squote!((#phase_ty, #left_ty))
+    }
+
+    /// Get the ingoing type of the phases. Advancing requires the phase to not
+    /// be past the phase advanced to, which is enforced by the phases left.
+    fn in_ty(&self) -> Type {
+        if let Some(exact) = self.exact {
+            return self.exact_ty(exact);
+        }
+
+        let min_left = self.advance.map_or(0, |(advance, _)| self.last() - advance);
+
+        let phase_ty = succ_ty(self.min_phase, parse_squote!(#{self.phases.ident}));
+        let left_ty = succ_ty(min_left, parse_squote!(#{self.left_ident()}));
+        parse_squote!((#phase_ty, #left_ty))
+    }
+
+    /// Get the outgoing type of the phases.
+    fn out_ty(&self) -> Type {
+        match self.advance {
+            Some((advance, _)) => self.exact_ty(advance),
+            None => self.in_ty(),
+        }
+    }
+
+    /// Get the type of the phases when constructed, which start at the preset
+    /// phase, or the first phase.
+    fn constructed_ty(&self, stateset: &Stateset) -> Type {
+        let phase = self.advance.map(|(advance, _)| advance).or_else(|| {
+            stateset["preset"]
+                .iter()
+                .find_map(|state| state.phase_index(self.phases))
+        });
+
+        self.exact_ty(phase.unwrap_or_default())
+    }
+}
+
+/// Get the generic parameters that replace the designated parameter of
+/// `group`. These are the states that are neither asserted nor rejected.
+fn states_generic_params(
+    stateset: &Stateset,
+    ruleset: &Stateset,
+    group: &Group,
+) -> Vec<GenericParam> {
+    group
+        .slots(stateset)
+        .flat_map(|slot| match slot {
+            Slot::Flag(slot) | Slot::Exclusive(slot) => {
+                if find_state(&ruleset["assert"], slot).is_some()
+                    || find_state(&ruleset["reject"], slot).is_some()
+                {
+                    Vec::new()
+                } else {
+                    vec![parse_squote!(#{slot.ident})]
+                }
+            }
+            Slot::Parameterized(slot) => {
+                if let Some(state) = find_state(&ruleset["assert"], slot) {
+                    // Asserting without a type keeps the carried type generic.
+                    match carried_ty(state) {
+                        Some(_) => Vec::new(),
+                        None => vec![parse_squote!(#{slot_param_ident(slot)})],
+                    }
+                } else if find_state(&ruleset["reject"], slot).is_some() {
+                    Vec::new()
+                } else {
+                    vec![parse_squote!(#{slot.ident})]
+                }
+            }
+            Slot::Counter(slot) => CounterRules::new(slot, ruleset).generic_params(),
+            Slot::Phases(slot) => PhaseRules::new(slot, ruleset).generic_params(),
+        })
+        .collect()
+}
+
+/// Get the type parameter that names the type carried by the parameterized
+/// state `slot`.
+fn slot_param_ident(slot: &State) -> &Ident {
+    slot.ty
+        .as_deref()
+        .and_then(param_ident)
+        .expect("parameterized states are declared with a type parameter")
+}
+
+/// Get the type of the parameterized state `slot` when enabled by `state`.
+fn with_ty(slot: &State, state: &State) -> Type {
+    match carried_ty(state) {
+        Some(ty) => parse_squote!(::stated::With<#ty>),
+        None => parse_squote!(::stated::With<#{slot_param_ident(slot)}>),
+    }
+}
+
+/// Get the ingoing type of the parameterized state `slot`.
+fn parameterized_in_ty(slot: &State, ruleset: &Stateset) -> Type {
+    if let Some(state) = find_state(&ruleset["assert"], slot) {
+        with_ty(slot, state)
+    } else if find_state(&ruleset["reject"], slot).is_some() {
+        parse_squote!(::stated::N)
+    } else {
+        parse_squote!(#{slot.ident})
+    }
+}
+
+/// Get the ingoing type of the state `slot`.
+fn flag_in_ty(slot: &State, ruleset: &Stateset) -> Type {
+    if ruleset["assert"].contains(slot) {
+        parse_squote!(::stated::Y)
+    } else if ruleset["reject"].contains(slot) {
+        parse_squote!(::stated::N)
+    } else {
+        parse_squote!(#{slot.ident})
+    }
+}
+
+/// Get the type that evaluates `expr` with the ingoing states.
+fn expr_ty(expr: &StateExpr, ruleset: &Stateset) -> Type {
+    match expr {
+        StateExpr::State(state) => flag_in_ty(state, ruleset),
+        StateExpr::Any(operands) => operands
+            .iter()
+            .map(|operand| expr_ty(operand, ruleset))
+            .reduce(|acc, ty| parse_squote!(<#acc as ::stated::Bit>::Or<#ty>))
+            .unwrap_or_else(|| parse_squote!(::stated::N)),
+        StateExpr::All(operands) => operands
+            .iter()
+            .map(|operand| expr_ty(operand, ruleset))
+            .reduce(|acc, ty| parse_squote!(<#acc as ::stated::Bit>::And<#ty>))
+            .unwrap_or_else(|| parse_squote!(::stated::Y)),
+        StateExpr::Not(operand) => {
+            let operand_ty = expr_ty(operand, ruleset);
+            parse_squote!(<#operand_ty as ::stated::Bit>::Not)
+        }
+    }
+}
+
+/// Add the predicates that enforce the asserted and rejected expressions in
+/// `ruleset` to `generics`, and that evaluate its conditional effects, toggles,
+/// and copies.
+fn add_expr_predicates(generics: &mut Generics, ruleset: &Stateset) {
+    let asserted_exprs = ruleset.exprs("assert");
+    let rejected_exprs = ruleset.exprs("reject");
+    let conditionals = ruleset.conditionals("when");
+
+    if asserted_exprs.is_empty()
+        && rejected_exprs.is_empty()
+        && conditionals.is_empty()
+        && ruleset["toggle"].is_empty()
+        && ruleset["copy"].is_empty()
+    {
+        return;
+    }
+
+    let predicates = &mut generics.make_where_clause().predicates;
+
+    // The states that are still generic must be bits to be evaluated.
+    let generic_states = asserted_exprs
+        .iter()
+        .chain(rejected_exprs)
+        .chain(
+            conditionals
+                .iter()
+                .map(|conditional| &conditional.condition),
+        )
+        .flat_map(StateExpr::states)
+        .chain(&ruleset["toggle"])
+        .chain(ruleset["copy"].iter().flat_map(|copy| &copy.members))
+        .filter(|state| !ruleset["assert"].contains(state))
+        .filter(|state| !ruleset["reject"].contains(state))
+        .map(|state| &state.ident)
+        .unique();
+
+    for ident in generic_states {
+        predicates.push(parse_squote!(#ident: ::stated::Bit));
+    }
+
+    for expr in asserted_exprs {
+        let expr_ty = expr_ty(expr, ruleset);
+        predicates.push(parse_squote!(#expr_ty: ::stated::True));
+    }
+
+    for expr in rejected_exprs {
+        let expr_ty = expr_ty(expr, ruleset);
+        predicates.push(parse_squote!(<#expr_ty as ::stated::Bit>::Not: ::stated::True));
+    }
+}
+
+/// Get the ingoing states type of `group`.
+fn states_in_ty(stateset: &Stateset, ruleset: &Stateset, group: &Group) -> Type {
+    let states_in_ty = group.slots(stateset).map(|slot| -> Type {
+        match slot {
+            Slot::Flag(slot) => flag_in_ty(slot, ruleset),
+            Slot::Parameterized(slot) => parameterized_in_ty(slot, ruleset),
+            Slot::Exclusive(slot) => match find_state(&ruleset["assert"], slot) {
+                Some(state) => choice_ty(slot, state),
+                None => parse_squote!(#{slot.ident}),
+            },
+            Slot::Counter(slot) => CounterRules::new(slot, ruleset).in_ty(),
+            Slot::Phases(slot) => PhaseRules::new(slot, ruleset).in_ty(),
+        }
+    });
+
+    parse_squote!((#(#states_in_ty),*))
+}
+
+/// Get the outgoing states type of `group`.
+fn states_out_ty(stateset: &Stateset, ruleset: &Stateset, group: &Group) -> Type {
+    let states_out_ty = group.slots(stateset).map(|slot| -> Type {
+        match slot {
+            Slot::Flag(slot) => {
+                if ruleset["assign"].contains(slot) {
+                    parse_squote!(::stated::Y)
+                } else if ruleset["delete"].contains(slot) {
+                    parse_squote!(::stated::N)
+                } else if ruleset["toggle"].contains(slot) {
+                    let in_ty = flag_in_ty(slot, ruleset);
+                    parse_squote!(<#in_ty as ::stated::Bit>::Not)
+                } else if let Some(copy) = find_state(&ruleset["copy"], slot) {
+                    flag_in_ty(&copy.members[0], ruleset)
+                } else {
+                    conditional_out_ty(slot, ruleset)
+                }
+            }
+            Slot::Parameterized(slot) => {
+                if let Some(state) = find_state(&ruleset["assign"], slot) {
+                    with_ty(slot, state)
+                } else if find_state(&ruleset["delete"], slot).is_some() {
+                    parse_squote!(::stated::N)
+                } else {
+                    parameterized_in_ty(slot, ruleset)
+                }
+            }
+            Slot::Exclusive(slot) => match find_state(&ruleset["assign"], slot)
+                .or_else(|| find_state(&ruleset["assert"], slot))
+            {
+                Some(state) => choice_ty(slot, state),
+                None => parse_squote!(#{slot.ident}),
+            },
+            Slot::Counter(slot) => CounterRules::new(slot, ruleset).out_ty(),
+            Slot::Phases(slot) => PhaseRules::new(slot, ruleset).out_ty(),
+        }
+    });
+
+    parse_squote!((#(#states_out_ty),*))
+}
+
+/// Get the outgoing type of the state `slot` that isn't transitioned
+/// unconditionally. Each conditional effect on `slot` selects its value if its
+/// condition holds, and the previous type otherwise, so later effects win.
+fn conditional_out_ty(slot: &State, ruleset: &Stateset) -> Type {
+    ruleset
+        .conditionals("when")
+        .iter()
+        .fold(flag_in_ty(slot, ruleset), |ty, conditional| {
+            let value_ty: Type = if conditional.effects["assign"].contains(slot) {
+                parse_squote!(::stated::Y)
+            } else if conditional.effects["delete"].contains(slot) {
+                parse_squote!(::stated::N)
+            } else {
+                return ty;
+            };
+
+            let condition_ty = expr_ty(&conditional.condition, ruleset);
+            parse_squote!(<#condition_ty as ::stated::Bit>::Select<#value_ty, #ty>)
+        })
+}
+
+/// Get the states type of `group` when constructed without a receiver.
+/// Exclusive groups that aren't assigned or preset start at their first choice.
+fn states_constructed_ty(stateset: &Stateset, ruleset: &Stateset, group: &Group) -> Type {
+    let states_constructed_ty = group.slots(stateset).map(|slot| -> Type {
+        match slot {
+            Slot::Flag(slot) => {
+                if ruleset["assign"].contains(slot) {
+                    parse_squote!(::stated::Y)
+                } else if ruleset["delete"].contains(slot) {
+                    parse_squote!(::stated::N)
+                } else if stateset["preset"].contains(slot) {
+                    parse_squote!(::stated::Y)
+                } else {
+                    parse_squote!(::stated::N)
+                }
+            }
+            Slot::Parameterized(slot) => {
+                if let Some(state) = find_state(&ruleset["assign"], slot) {
+                    with_ty(slot, state)
+                } else if find_state(&ruleset["delete"], slot).is_some() {
+                    parse_squote!(::stated::N)
+                } else if let Some(state) = find_state(&stateset["preset"], slot) {
+                    with_ty(slot, state)
+                } else {
+                    parse_squote!(::stated::N)
+                }
+            }
+            Slot::Exclusive(slot) => match find_state(&ruleset["assign"], slot)
+                .or_else(|| find_state(&stateset["preset"], slot))
+            {
+                Some(state) => choice_ty(slot, state),
+                None => parse_squote!(::stated::Choice<0>),
+            },
+            Slot::Counter(slot) => CounterRules::new(slot, ruleset).constructed_ty(),
+            Slot::Phases(slot) => PhaseRules::new(slot, ruleset).constructed_ty(stateset),
+        }
+    });
+
+    parse_squote!((#(#states_constructed_ty),*))
+}
+
+/// Replace `_` in the body of the method `associated_fn` with its receiver
+/// reconstructed. Receivers in a `Box`, `Rc`, or `Arc` are reconstructed in the
+/// same kind of pointer.
+fn replace_expr_infer(associated_fn: &mut ImplItemFn, self_ident: &Ident) -> Result<()> {
+    let Some(receiver) = associated_fn.sig.receiver() else {
+        return Ok(());
+    };
+
+    let receiver_span = receiver.span();
+
+    let reconstruct_ident = match receiver.ty.as_ref() {
+        Type::Path(TypePath { qself: None, path }) => path.segments.last().and_then(|seg| {
+            if seg.ident == "Self" || seg.ident == *self_ident {
+                Some("__reconstruct")
+            } else if seg.ident == "Box" {
+                Some("__reconstruct_box")
+            } else if seg.ident == "Rc" {
+                Some("__reconstruct_rc")
+            } else if seg.ident == "Arc" {
+                Some("__reconstruct_arc")
+            } else {
+                None
+            }
+        }),
+        _ => None,
+    };
+
+    let Some(reconstruct_ident) = reconstruct_ident else {
+        // Validate `_` isn't used, since the receiver can't be reconstructed.
+        let mut finder = FindExprInfer::default();
+        finder.visit_block_mut(&mut associated_fn.block);
+
+        if let Some(span) = finder.0 {
+            return Err(Error::new(
+                span,
+                "only methods that take `self` by value or in a `Box`, `Rc`, or `Arc` can reconstruct it",
+            ));
+        }
+
+        return Ok(());
+    };
+
+    // Only the by-value reconstruct method is const, since the pointers can't be
+    // allocated in a const fn.
+    if associated_fn.sig.constness.is_some() && reconstruct_ident != "__reconstruct" {
+        let mut finder = FindExprInfer::default();
+        finder.visit_block_mut(&mut associated_fn.block);
+
+        if let Some(span) = finder.0 {
+            return Err(Error::new(
+                span,
+                "only methods that take `self` by value can reconstruct it in a const fn",
+            ));
+        }
+    }
+
+    let reconstruct_ident = Ident::new(reconstruct_ident, receiver_span);
+
+    ReplaceExprInfer(parse_squote!(@receiver_span=> self.#reconstruct_ident()))
+        .visit_block_mut(&mut associated_fn.block);
+
+    Ok(())
+}
+
+/// Replace `_` in the signature of `impl_item` with `replacer`.
+fn replace_impl_item_type_infer(impl_item: &mut ImplItem, replacer: &mut dyn VisitMut) {
+    match impl_item {
+        ImplItem::Fn(associated_fn) => {
+            replacer.visit_return_type_mut(&mut associated_fn.sig.output)
+        }
+        ImplItem::Type(associated_ty) => replacer.visit_type_mut(&mut associated_ty.ty),
+        ImplItem::Const(associated_const) => replacer.visit_type_mut(&mut associated_const.ty),
+        _ => {}
+    }
+}
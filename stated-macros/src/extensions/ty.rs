@@ -1,5 +1,5 @@
 use extend::ext;
-use syn::{Error, PathArguments, PathSegment, Result, Type, TypePath, spanned::Spanned};
+use syn::{Error, Ident, PathArguments, PathSegment, Result, Type, TypePath, spanned::Spanned};
 
 #[ext]
 pub impl Type {
@@ -18,6 +18,14 @@ pub impl Type {
             _ => Err(Error::new(self.span(), "expected a path")),
         }
     }
+
+    /// Check if the type is just the type parameter `ident`.
+    fn is_param(&self, ident: &Ident) -> bool {
+        match self {
+            Self::Path(TypePath { qself: None, path }) => path.is_ident(ident),
+            _ => false,
+        }
+    }
 }
 
 #[ext]
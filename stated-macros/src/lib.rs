@@ -2,13 +2,16 @@
 
 use proc_macro::TokenStream;
 use syn::{
-    Error, Item, Meta, Token, parse::Nothing, parse_macro_input, punctuated::Punctuated,
+    DeriveInput, Error, Item, Meta, Token, parse_macro_input, punctuated::Punctuated,
     spanned::Spanned,
 };
 
 use crate::expand::{
-    stated::{expand_item_impl, expand_item_struct},
-    stated_internal::{expand_item_impl_internal, expand_item_struct_internal},
+    stated::{expand_item_enum, expand_item_impl, expand_item_struct},
+    stated_builder::expand_derive_stated_builder,
+    stated_internal::{
+        expand_item_enum_internal, expand_item_impl_internal, expand_item_struct_internal,
+    },
 };
 
 mod expand;
@@ -24,11 +27,15 @@ pub fn stated(args: TokenStream, input: TokenStream) -> TokenStream {
             let metas = parse_macro_input!(args with Punctuated<Meta, Token![,]>::parse_terminated);
             expand_item_struct(metas, item_struct)
         }
+        Item::Enum(item_enum) => {
+            let metas = parse_macro_input!(args with Punctuated<Meta, Token![,]>::parse_terminated);
+            expand_item_enum(metas, item_enum)
+        }
         Item::Impl(item_impl) => {
-            parse_macro_input!(args as Nothing);
-            expand_item_impl(item_impl)
+            let metas = parse_macro_input!(args with Punctuated<Meta, Token![,]>::parse_terminated);
+            expand_item_impl(metas, item_impl)
         }
-        other => Err(Error::new(other.span(), "expected a struct or impl")),
+        other => Err(Error::new(other.span(), "expected a struct, enum, or impl")),
     };
 
     result.unwrap_or_else(Error::into_compile_error).into()
@@ -41,9 +48,19 @@ pub fn stated_internal(args: TokenStream, input: TokenStream) -> TokenStream {
 
     let result = match item {
         Item::Struct(item_struct) => expand_item_struct_internal(metas, item_struct),
+        Item::Enum(item_enum) => expand_item_enum_internal(metas, item_enum),
         Item::Impl(item_impl) => expand_item_impl_internal(metas, item_impl),
-        other => Err(Error::new(other.span(), "expected a struct or impl")),
+        other => Err(Error::new(other.span(), "expected a struct, enum, or impl")),
     };
 
     result.unwrap_or_else(Error::into_compile_error).into()
 }
+
+#[proc_macro_derive(StatedBuilder, attributes(builder))]
+pub fn stated_builder(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_derive_stated_builder(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
@@ -1,14 +1,24 @@
 use syn::{
-    Error, GenericArgument, GenericParam, Ident, Result, Token, Type, TypePath,
+    Error, GenericArgument, GenericParam, Ident, Meta, Result, Token, Type, TypePath,
     punctuated::Punctuated, spanned::Spanned,
 };
 
 use crate::extensions::generics::GenericParamExt;
 
-pub fn get_designated_indices(
+/// A designated parameter.
+pub struct Designated {
+    /// The index of the parameter.
+    pub param_index: usize,
+    /// The index of the designating attribute on the parameter.
+    pub attr_index: usize,
+    /// The group named by the designating attribute.
+    pub group: Option<Ident>,
+}
+
+pub fn get_designated_params(
     params: &Punctuated<GenericParam, Token![,]>,
-) -> Result<(usize, usize)> {
-    let mut designated_indices = None;
+) -> Result<Vec<Designated>> {
+    let mut designated_params: Vec<Designated> = Vec::new();
 
     for (param_index, param) in params.iter().enumerate() {
         // Only type parameters can be designated, so skip the other variants.
@@ -16,18 +26,32 @@ pub fn get_designated_indices(
             continue;
         };
 
-        let mut designating_attr_index = None;
+        let mut designated = None;
 
         for (attr_index, attr) in ty_param.attrs.iter().enumerate() {
             if !attr.path().is_ident("stated") {
                 continue;
             }
 
-            // Validate the attribute is just a path.
-            attr.meta.require_path_only()?;
+            // Get the group, if the attribute names one.
+            let group = match &attr.meta {
+                Meta::List(meta_list) => Some(meta_list.parse_args::<Ident>()?),
+                meta => {
+                    // Validate the attribute is just a path.
+                    meta.require_path_only()?;
+                    None
+                }
+            };
 
-            // Set the index.
-            if designating_attr_index.replace(attr_index).is_some() {
+            // Set the designated parameter.
+            if designated
+                .replace(Designated {
+                    param_index,
+                    attr_index,
+                    group,
+                })
+                .is_some()
+            {
                 // TODO(blocked): Emit a warning once procedural macro diagnostics is
                 // stabilized. Tracking issue: https://github.com/rust-lang/rust/issues/54140.
                 return Err(Error::new(attr.span(), "parameter is already designated"));
@@ -35,23 +59,47 @@ pub fn get_designated_indices(
         }
 
         // If there is no designating attribute, continue to the next parameter.
-        let Some(designating_attr_index) = designating_attr_index else {
+        let Some(designated) = designated else {
             continue;
         };
 
-        // Set the designated indices. Validate only one parameter was designated.
-        if designated_indices
-            .replace((param_index, designating_attr_index))
-            .is_some()
+        // Validate no other parameter designates the same group.
+        if designated_params
+            .iter()
+            .any(|other| other.group == designated.group)
         {
-            return Err(Error::new(
-                param.span(),
-                "cannot designate more than one parameter",
-            ));
+            return Err(match &designated.group {
+                Some(group) => Error::new(group.span(), "group is already designated"),
+                None => Error::new(
+                    ty_param.ident.span(),
+                    "multiple designated parameters must each name a group",
+                ),
+            });
         }
+
+        designated_params.push(designated);
+    }
+
+    // Validate every parameter names a group if there are multiple.
+    if designated_params.len() > 1
+        && let Some(designated) = designated_params
+            .iter()
+            .find(|designated| designated.group.is_none())
+    {
+        return Err(Error::new(
+            params[designated.param_index]
+                .require_type_param()?
+                .ident
+                .span(),
+            "multiple designated parameters must each name a group",
+        ));
+    }
+
+    if designated_params.is_empty() {
+        return Err(Error::new(params.span(), "no parameter is designated"));
     }
 
-    designated_indices.ok_or_else(|| Error::new(params.span(), "no parameter is designated"))
+    Ok(designated_params)
 }
 
 pub fn find_designated_arg(
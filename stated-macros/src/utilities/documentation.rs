@@ -82,9 +82,20 @@ impl<'a> Description<'a> {
                 .stateset
                 .get(&line.kind)
                 .expect("unsupported state kind");
+            let exprs = self.stateset.exprs(&line.kind);
+            let conditionals = self.stateset.conditionals(&line.kind);
 
             let label = line.label.unwrap_or(line.kind);
-            let states = states.iter().map(|state| state.to_string()).join(", ");
+            let states = states
+                .iter()
+                .map(|state| state.to_string())
+                .chain(exprs.iter().map(|expr| expr.to_string()))
+                .chain(
+                    conditionals
+                        .iter()
+                        .map(|conditional| conditional.to_string()),
+                )
+                .join(", ");
 
             // Don't write this line if there are no states to list.
             if states.is_empty() {
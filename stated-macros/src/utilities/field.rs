@@ -0,0 +1,181 @@
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::{
+    Attribute, Error, Field, Ident, Meta, Result, Token, Type, parenthesized, parse::ParseStream,
+    punctuated::Punctuated, spanned::Spanned,
+};
+
+use crate::utilities::{squote::parse_squote, stateset::State};
+
+/// The stated attributes of a field.
+#[derive(Default)]
+pub struct FieldAttrs {
+    /// The type of the field, if it depends on a state.
+    pub dependent_ty: Option<DependentTy>,
+    /// The accessors of the field.
+    pub accessors: Vec<Accessor>,
+}
+
+impl FieldAttrs {
+    /// Parse the stated attributes of `field`.
+    pub fn from_field(field: &Field) -> Result<Self> {
+        let mut field_attrs = Self::default();
+
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("stated"))
+        {
+            if !is_dependent_ty_attr(attr) {
+                field_attrs.accessors.extend(Accessor::from_attr(attr)?);
+                continue;
+            }
+
+            // Validate there is at most one dependent type.
+            if field_attrs.dependent_ty.is_some() {
+                return Err(Error::new(
+                    attr.span(),
+                    "fields cannot have more than one dependent type",
+                ));
+            }
+
+            field_attrs.dependent_ty = Some(DependentTy::from_attr(attr)?);
+        }
+
+        Ok(field_attrs)
+    }
+}
+
+/// Check if `attr` declares a dependent type, which starts with `when` or
+/// `otherwise`.
+fn is_dependent_ty_attr(attr: &Attribute) -> bool {
+    attr.parse_args_with(|input: ParseStream| {
+        let ident = input.parse::<Ident>()?;
+        input.parse::<TokenStream2>()?;
+
+        Ok(ident == "when" || ident == "otherwise")
+    })
+    .unwrap_or(false)
+}
+
+/// The type of a field that depends on a state, like
+/// `#[stated(when(HasBody) = String, otherwise = ())]`.
+pub struct DependentTy {
+    /// The state the type depends on.
+    pub state: State,
+    /// The type of the field when the state is enabled.
+    pub when_ty: Type,
+    /// The type of the field when the state is disabled, which defaults to `()`.
+    pub otherwise_ty: Type,
+}
+
+impl DependentTy {
+    /// Parse the dependent type from `attr`.
+    fn from_attr(attr: &Attribute) -> Result<Self> {
+        let mut when = None;
+        let mut otherwise_ty = None;
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("when") {
+                if when.is_some() {
+                    return Err(meta.error("redundant `when` attribute"));
+                }
+
+                let content;
+                parenthesized!(content in meta.input);
+                let state = State::from_path(&content.parse()?)?;
+
+                when = Some((state, meta.value()?.parse()?));
+            } else if meta.path.is_ident("otherwise") {
+                if otherwise_ty.is_some() {
+                    return Err(meta.error("redundant `otherwise` attribute"));
+                }
+
+                otherwise_ty = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("invalid attribute"));
+            }
+
+            Ok(())
+        })?;
+
+        let Some((state, when_ty)) = when else {
+            return Err(Error::new(
+                attr.path().span(),
+                "expected a `when` attribute",
+            ));
+        };
+
+        Ok(Self {
+            state,
+            when_ty,
+            otherwise_ty: otherwise_ty.unwrap_or_else(|| parse_squote!(())),
+        })
+    }
+}
+
+/// An accessor of a field that is restricted by a ruleset, like
+/// `#[stated(get, assert(HasRecipient))]`.
+pub struct Accessor {
+    /// Whether the accessor returns a mutable reference, which is `get_mut`.
+    pub mutable: bool,
+    /// The span of `get` or `get_mut`.
+    pub span: Span,
+    /// The ruleset of the accessor.
+    pub ruleset: Punctuated<Meta, Token![,]>,
+}
+
+impl Accessor {
+    /// Parse the accessors from `attr`. Both `get` and `get_mut` share the
+    /// ruleset of the attribute.
+    fn from_attr(attr: &Attribute) -> Result<Vec<Self>> {
+        let metas = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+
+        let mut kinds = Vec::new();
+        let mut ruleset = Punctuated::new();
+
+        for meta in metas {
+            let mutable = if meta.path().is_ident("get") {
+                false
+            } else if meta.path().is_ident("get_mut") {
+                true
+            } else {
+                // Validate the ruleset doesn't transition states, since accessors only
+                // borrow the struct.
+                if !meta.path().is_ident("assert") && !meta.path().is_ident("reject") {
+                    return Err(Error::new(
+                        meta.path().span(),
+                        "accessors can only assert or reject states",
+                    ));
+                }
+
+                ruleset.push(meta);
+                continue;
+            };
+
+            meta.require_path_only()?;
+
+            // Validate each accessor is generated once.
+            if kinds.iter().any(|(other, _)| *other == mutable) {
+                return Err(Error::new(meta.span(), "accessor is already generated"));
+            }
+
+            kinds.push((mutable, meta.span()));
+        }
+
+        if kinds.is_empty() {
+            return Err(Error::new(
+                attr.path().span(),
+                "expected `get` or `get_mut`",
+            ));
+        }
+
+        Ok(kinds
+            .into_iter()
+            .map(|(mutable, span)| Self {
+                mutable,
+                span,
+                ruleset: ruleset.clone(),
+            })
+            .collect())
+    }
+}
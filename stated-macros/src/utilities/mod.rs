@@ -1,5 +1,6 @@
 pub mod designated;
 pub mod documentation;
+pub mod field;
 pub mod stateset;
 pub mod visit;
 
@@ -1,26 +1,676 @@
-use std::{collections::HashMap, ops::Deref};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
 
-use syn::{Ident, Meta, Result};
+use itertools::Itertools;
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::ToTokens;
+use syn::{
+    Error, Ident, LitInt, Meta, Path, Result, Token, Type, braced,
+    meta::{self, ParseNestedMeta},
+    parse::{ParseStream, Parser},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token::{Brace, Paren},
+};
 
-/// A map of state kinds to state identifiers.
+/// A comparison between a counter and a value.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Comparison {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    /// Parse a comparison from `input`, if there is one.
+    fn parse(input: ParseStream) -> Result<Option<Self>> {
+        // Two-character comparisons are checked first, since `<` and `>` are a prefix
+        // of them.
+        let comparison = if input.peek(Token![==]) {
+            input.parse::<Token![==]>()?;
+            Self::Eq
+        } else if input.peek(Token![<=]) {
+            input.parse::<Token![<=]>()?;
+            Self::Le
+        } else if input.peek(Token![>=]) {
+            input.parse::<Token![>=]>()?;
+            Self::Ge
+        } else if input.peek(Token![<]) {
+            input.parse::<Token![<]>()?;
+            Self::Lt
+        } else if input.peek(Token![>]) {
+            input.parse::<Token![>]>()?;
+            Self::Gt
+        } else {
+            return Ok(None);
+        };
+
+        Ok(Some(comparison))
+    }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Eq => "==",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+        })
+    }
+}
+
+/// A bound on a counter, like `>= 2`.
+#[derive(Clone)]
+pub struct Bound {
+    pub comparison: Comparison,
+    pub value: usize,
+    pub span: Span,
+}
+
+impl Bound {
+    /// Parse the value of a bound from `input`.
+    fn parse_value(comparison: Comparison, input: ParseStream) -> Result<Self> {
+        let lit: LitInt = input.parse()?;
+
+        Ok(Self {
+            comparison,
+            value: lit.base10_parse()?,
+            span: lit.span(),
+        })
+    }
+}
+
+impl PartialEq for Bound {
+    fn eq(&self, other: &Self) -> bool {
+        self.comparison == other.comparison && self.value == other.value
+    }
+}
+
+impl Eq for Bound {}
+
+impl Hash for Bound {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.comparison.hash(state);
+        self.value.hash(state);
+    }
+}
+
+/// A state, optionally qualified by the group it belongs to. Exclusive groups
+/// are states with choices, like `Phase = Idle | Running`. Counters may have a
+/// bound, like `Retries >= 2`. Aliases are states with members, like
+/// `Complete = HasSubject + HasBody`, and copies have the state they copy as
+/// their only member, like `Dirty = Modified`. Phases are declared as a state
+/// named `Phase` with the phases, in order, as its choices. Substates, like
+/// `Authenticated` in `Connected { Authenticated }`, know their parent state.
+/// Parameterized states carry a type, like `Encoding<E>` when declared and
+/// `Encoding<Gzip>` or `Encoding<_>` in rules.
+#[derive(Clone)]
+pub struct State {
+    pub group: Option<Ident>,
+    pub ident: Ident,
+    pub choices: Vec<Ident>,
+    pub bound: Option<Bound>,
+    pub members: Vec<State>,
+    /// The parent state, which is ignored when comparing states, since rules
+    /// name substates without it.
+    pub parent: Option<Ident>,
+    /// The carried type, which is ignored when comparing states, since rules
+    /// carry different types than the declaration.
+    pub ty: Option<Box<Type>>,
+}
+
+impl State {
+    /// Parse a state from `path`, which is either `State` or `group::State`.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        if path.leading_colon.is_some()
+            || path.segments.len() > 2
+            || path.segments.iter().any(|seg| !seg.arguments.is_none())
+        {
+            return Err(Error::new(path.span(), "expected a state"));
+        }
+
+        let mut idents = path.segments.iter().map(|seg| seg.ident.clone());
+        let first = idents.next().expect("path is not empty");
+
+        Ok(match idents.next() {
+            Some(ident) => Self {
+                group: Some(first),
+                ident,
+                choices: Vec::new(),
+                bound: None,
+                members: Vec::new(),
+                parent: None,
+                ty: None,
+            },
+            None => Self {
+                group: None,
+                ident: first,
+                choices: Vec::new(),
+                bound: None,
+                members: Vec::new(),
+                parent: None,
+                ty: None,
+            },
+        })
+    }
+
+    /// Parse a state from `meta`, which is either a state, an exclusive group
+    /// followed by its choices, like `Phase = Idle | Running`, or a counter
+    /// followed by a bound, like `Retries >= 2`.
+    pub fn from_nested_meta(meta: &ParseNestedMeta) -> Result<Self> {
+        let mut state = Self::from_path(&meta.path)?;
+
+        // A counter compared with `<` is followed by a value rather than a type.
+        if meta.input.peek(Token![<]) && !meta.input.peek(Token![<=]) && !meta.input.peek2(LitInt) {
+            meta.input.parse::<Token![<]>()?;
+            state.ty = Some(meta.input.parse()?);
+            meta.input.parse::<Token![>]>()?;
+        }
+
+        if let Some(comparison) = Comparison::parse(meta.input)? {
+            state.bound = Some(Bound::parse_value(comparison, meta.input)?);
+        } else if meta.input.peek(Token![=]) {
+            state.choices =
+                Punctuated::<Ident, Token![|]>::parse_separated_nonempty(meta.value()?)?
+                    .into_iter()
+                    .collect();
+        }
+
+        Ok(state)
+    }
+
+    /// Parse a counter from `meta`, like `counter(Retries, max = 3)`. The max is
+    /// stored as a `<=` bound.
+    pub fn from_counter_meta(meta: &Meta) -> Result<Self> {
+        let mut counter: Option<Self> = None;
+        let mut max = None;
+
+        meta.require_list()?.parse_nested_meta(|meta| {
+            if meta.path.is_ident("max") {
+                if max.is_some() {
+                    return Err(meta.error("redundant `max` attribute"));
+                }
+
+                max = Some(Bound::parse_value(Comparison::Le, meta.value()?)?);
+            } else if counter.is_none() {
+                counter = Some(Self::from_path(&meta.path)?);
+            } else {
+                return Err(meta.error("invalid attribute"));
+            }
+
+            Ok(())
+        })?;
+
+        let mut counter = counter.ok_or_else(|| Error::new(meta.span(), "expected a counter"))?;
+        counter.bound = max;
+
+        Ok(counter)
+    }
+
+    /// Parse an alias from `meta`, like `Complete = HasSubject + HasBody`.
+    pub fn from_alias_meta(meta: &ParseNestedMeta) -> Result<Self> {
+        let mut alias = Self::from_path(&meta.path)?;
+
+        alias.members = Punctuated::<Path, Token![+]>::parse_separated_nonempty(meta.value()?)?
+            .iter()
+            .map(Self::from_path)
+            .try_collect()?;
+
+        Ok(alias)
+    }
+
+    /// Parse a state and its substates from `meta` into `states`, like
+    /// `Connected { Authenticated, Encrypted }`. Substates belong to the group
+    /// of their parent.
+    fn parse_nested(
+        meta: &ParseNestedMeta,
+        parent: Option<&Self>,
+        states: &mut Vec<Self>,
+    ) -> Result<()> {
+        let mut state = Self::from_nested_meta(meta)?;
+
+        if let Some(parent) = parent {
+            if let Some(group) = &state.group {
+                return Err(Error::new(
+                    group.span(),
+                    "substates belong to the group of their parent",
+                ));
+            }
+
+            state.group = parent.group.clone();
+            state.parent = Some(parent.ident.clone());
+        }
+
+        states.push(state.clone());
+
+        if meta.input.peek(Brace) {
+            let content;
+            braced!(content in meta.input);
+
+            meta::parser(|meta| Self::parse_nested(&meta, Some(&state), states))
+                .parse2(content.parse()?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Parse the phases from `meta`, like `phases(Created, Connected, Closed)`.
+    /// Qualifying the first phase with a group puts all phases in that group.
+    pub fn from_phases_meta(meta: &Meta) -> Result<Self> {
+        let mut group = None;
+        let mut choices = Vec::new();
+
+        meta.require_list()?.parse_nested_meta(|meta| {
+            let phase = Self::from_path(&meta.path)?;
+
+            if choices.is_empty() {
+                group = phase.group;
+            } else if phase.group.is_some() && phase.group != group {
+                return Err(Error::new(
+                    phase.span(),
+                    "phases must belong to the same group",
+                ));
+            }
+
+            choices.push(phase.ident);
+            Ok(())
+        })?;
+
+        if choices.is_empty() {
+            return Err(Error::new(meta.span(), "expected phases"));
+        }
+
+        Ok(Self {
+            group,
+            ident: Ident::new("Phase", meta.path().span()),
+            choices,
+            bound: None,
+            members: Vec::new(),
+            parent: None,
+            ty: None,
+        })
+    }
+
+    /// Get the index of `self` in the phases `phases`.
+    pub fn phase_index(&self, phases: &Self) -> Option<usize> {
+        if self.group != phases.group || !self.choices.is_empty() || self.bound.is_some() {
+            return None;
+        }
+
+        phases.choices.iter().position(|phase| *phase == self.ident)
+    }
+
+    /// Parse a copy from `meta`, like `Dirty = Modified`.
+    pub fn from_copy_meta(meta: &ParseNestedMeta) -> Result<Self> {
+        let mut copy = Self::from_path(&meta.path)?;
+        copy.members = vec![Self::from_path(&meta.value()?.parse()?)?];
+
+        Ok(copy)
+    }
+
+    /// Check if `other` is the same state, regardless of the choices and bound.
+    pub fn is_same(&self, other: &Self) -> bool {
+        self.group == other.group && self.ident == other.ident
+    }
+
+    /// Get the index of the choice of `self` in the exclusive group `other`.
+    pub fn choice_index(&self, other: &Self) -> Option<usize> {
+        let [choice] = self.choices.as_slice() else {
+            return None;
+        };
+
+        if !self.is_same(other) {
+            return None;
+        }
+
+        other.choices.iter().position(|other| other == choice)
+    }
+
+    /// Get the span of the state.
+    pub fn span(&self) -> Span {
+        self.ident.span()
+    }
+}
+
+impl PartialEq for State {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_same(other)
+            && self.choices == other.choices
+            && self.bound == other.bound
+            && self.members == other.members
+    }
+}
+
+impl Eq for State {}
+
+impl Hash for State {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.group.hash(state);
+        self.ident.hash(state);
+        self.choices.hash(state);
+        self.bound.hash(state);
+        self.members.hash(state);
+    }
+}
+
+impl Display for State {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.group {
+            Some(group) => write!(f, "{group}::{}", self.ident)?,
+            None => write!(f, "{}", self.ident)?,
+        }
+
+        if let Some(ty) = &self.ty {
+            let ty = [
+                (" :: ", "::"),
+                (" <", "<"),
+                ("< ", "<"),
+                (" >", ">"),
+                (" ,", ","),
+            ]
+            .into_iter()
+            .fold(ty.to_token_stream().to_string(), |ty, (from, to)| {
+                ty.replace(from, to)
+            });
+
+            write!(f, "<{ty}>")?;
+        }
+
+        if !self.choices.is_empty() {
+            write!(f, " = {}", self.choices.iter().join(" | "))?;
+        }
+
+        if let Some(bound) = &self.bound {
+            write!(f, " {} {}", bound.comparison, bound.value)?;
+        }
+
+        if !self.members.is_empty() {
+            write!(f, " = {}", self.members.iter().join(" + "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A boolean expression of states, like `any(A, not(B))`.
+#[derive(Clone, PartialEq, Eq)]
+pub enum StateExpr {
+    State(State),
+    Any(Vec<StateExpr>),
+    All(Vec<StateExpr>),
+    Not(Box<StateExpr>),
+}
+
+impl StateExpr {
+    /// Check if `meta` is the start of an expression rather than a state.
+    fn peek(meta: &ParseNestedMeta) -> bool {
+        (meta.path.is_ident("any") || meta.path.is_ident("all") || meta.path.is_ident("not"))
+            && meta.input.peek(Paren)
+    }
+
+    /// Parse an expression from `meta`.
+    fn from_nested_meta(meta: &ParseNestedMeta) -> Result<Self> {
+        if !Self::peek(meta) {
+            return State::from_nested_meta(meta).map(Self::State);
+        }
+
+        let mut operands = Vec::new();
+
+        meta.parse_nested_meta(|meta| {
+            operands.push(Self::from_nested_meta(&meta)?);
+            Ok(())
+        })?;
+
+        if meta.path.is_ident("any") {
+            Ok(Self::Any(operands))
+        } else if meta.path.is_ident("all") {
+            Ok(Self::All(operands))
+        } else {
+            let [operand] = <[_; 1]>::try_from(operands)
+                .map_err(|_| meta.error("expected exactly one operand"))?;
+
+            Ok(Self::Not(Box::new(operand)))
+        }
+    }
+
+    /// Replace the states in the expression that are `aliases` with all of
+    /// their members.
+    fn expand_aliases(&mut self, aliases: &[State]) {
+        match self {
+            Self::State(state) => {
+                if let Some(alias) = find_alias(aliases, state) {
+                    *self = Self::All(alias.members.iter().cloned().map(Self::State).collect());
+                }
+            }
+            Self::Any(operands) | Self::All(operands) => {
+                for operand in operands {
+                    operand.expand_aliases(aliases);
+                }
+            }
+            Self::Not(operand) => operand.expand_aliases(aliases),
+        }
+    }
+
+    /// Get the states in the expression.
+    pub fn states(&self) -> Vec<&State> {
+        match self {
+            Self::State(state) => vec![state],
+            Self::Any(operands) | Self::All(operands) => {
+                operands.iter().flat_map(Self::states).collect()
+            }
+            Self::Not(operand) => operand.states(),
+        }
+    }
+
+    /// Get the span of the expression.
+    pub fn span(&self) -> Span {
+        self.states()
+            .first()
+            .map_or_else(Span::call_site, |state| state.span())
+    }
+}
+
+impl Display for StateExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::State(state) => write!(f, "{state}"),
+            Self::Any(operands) => write!(f, "any({})", operands.iter().join(", ")),
+            Self::All(operands) => write!(f, "all({})", operands.iter().join(", ")),
+            Self::Not(operand) => write!(f, "not({operand})"),
+        }
+    }
+}
+
+/// Find the alias in `aliases` that `state` refers to.
+fn find_alias<'a>(aliases: &'a [State], state: &State) -> Option<&'a State> {
+    if !state.choices.is_empty() || state.bound.is_some() || !state.members.is_empty() {
+        return None;
+    }
+
+    aliases.iter().find(|alias| alias.is_same(state))
+}
+
+/// A conditional effect, like `when(Buffered => assign(Clean))`. The effects
+/// only apply if the condition holds for the ingoing states.
+#[derive(Clone)]
+pub struct Conditional {
+    pub condition: StateExpr,
+    pub effects: Stateset,
+    pub span: Span,
+}
+
+impl Conditional {
+    /// Parse a conditional effect from `meta`. Multiple conditions must all hold,
+    /// like `when(A, not(B) => assign(C))`.
+    fn from_meta(meta: &Meta) -> Result<Self> {
+        let list = meta.require_list()?;
+
+        let (conditions, effects) = list.parse_args_with(|input: ParseStream| {
+            let mut condition_tokens = TokenStream2::new();
+
+            while !input.peek(Token![=>]) {
+                if input.is_empty() {
+                    return Err(input.error("expected `=>`"));
+                }
+
+                condition_tokens.extend([input.parse::<TokenTree>()?]);
+            }
+
+            input.parse::<Token![=>]>()?;
+
+            let mut conditions = Vec::new();
+
+            meta::parser(|meta| {
+                conditions.push(StateExpr::from_nested_meta(&meta)?);
+                Ok(())
+            })
+            .parse2(condition_tokens)?;
+
+            let effects = Punctuated::<Meta, Token![,]>::parse_terminated(input)?;
+
+            Ok((conditions, effects))
+        })?;
+
+        let condition = match <[_; 1]>::try_from(conditions) {
+            Ok([condition]) => condition,
+            Err(conditions) if conditions.is_empty() => {
+                return Err(Error::new(
+                    list.delimiter.span().join(),
+                    "expected a condition",
+                ));
+            }
+            Err(conditions) => StateExpr::All(conditions),
+        };
+
+        // Validate the effects only transition states.
+        if let Some(effect) = effects
+            .iter()
+            .filter(|effect| !effect.path().is_ident("assign"))
+            .find(|effect| !effect.path().is_ident("delete"))
+        {
+            return Err(Error::new(effect.path().span(), "invalid attribute"));
+        }
+
+        if effects.is_empty() {
+            return Err(Error::new(
+                list.delimiter.span().join(),
+                "expected an effect",
+            ));
+        }
+
+        let mut conditional = Self {
+            condition,
+            effects: Stateset::default().support("assign").support("delete"),
+            span: meta.path().span(),
+        };
+
+        conditional.effects.extend_with_metas(&effects)?;
+
+        Ok(conditional)
+    }
+
+    /// Replace the states in the condition and effects that are `aliases` with
+    /// their members.
+    fn expand_aliases(&mut self, aliases: &[State]) {
+        self.condition.expand_aliases(aliases);
+        self.effects.expand_aliases(aliases);
+    }
+}
+
+impl PartialEq for Conditional {
+    fn eq(&self, other: &Self) -> bool {
+        self.condition == other.condition
+            && ["assign", "delete"].into_iter().all(|kind| {
+                self.effects[kind] == other.effects[kind]
+                    && self.effects.exprs(kind) == other.effects.exprs(kind)
+            })
+    }
+}
+
+impl Display for Conditional {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let effects = ["assign", "delete"]
+            .into_iter()
+            .filter(|kind| !self.effects[*kind].is_empty())
+            .map(|kind| format!("{kind}({})", self.effects[kind].iter().join(", ")))
+            .join(", ");
+
+        write!(f, "{} => {effects}", self.condition)
+    }
+}
+
+/// Get the ancestors of `state` declared in `declared`, from its parent up.
+fn ancestors(state: &State, declared: &[State]) -> Vec<State> {
+    let mut ancestors = Vec::new();
+    let mut current = declared.iter().find(|other| other.is_same(state));
+
+    while let Some(parent) = current.and_then(|current| current.parent.as_ref()) {
+        current = declared
+            .iter()
+            .find(|other| other.group == state.group && other.ident == *parent);
+        ancestors.extend(current.cloned());
+    }
+
+    ancestors
+}
+
+/// Get the descendants of `state` declared in `declared`.
+fn descendants(state: &State, declared: &[State]) -> Vec<State> {
+    declared
+        .iter()
+        .filter(|other| {
+            ancestors(other, declared)
+                .iter()
+                .any(|ancestor| ancestor.is_same(state))
+        })
+        .cloned()
+        .collect()
+}
+
+/// A map of state kinds to states. Expressions of states and conditional
+/// effects are kept separately, since most state kinds don't support them.
 #[derive(Default, Clone)]
-pub struct Stateset(HashMap<String, Vec<Ident>>);
+pub struct Stateset {
+    states: HashMap<String, Vec<State>>,
+    exprs: HashMap<String, Vec<StateExpr>>,
+    conditionals: HashMap<String, Vec<Conditional>>,
+}
 
 impl Deref for Stateset {
-    type Target = HashMap<String, Vec<Ident>>;
+    type Target = HashMap<String, Vec<State>>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.states
     }
 }
 
 impl Stateset {
     /// Add support for a state kind.
     pub fn support(mut self, kind: &str) -> Self {
-        self.0.insert(kind.to_string(), Vec::new());
+        self.states.insert(kind.to_string(), Vec::new());
+        self.exprs.insert(kind.to_string(), Vec::new());
+        self.conditionals.insert(kind.to_string(), Vec::new());
         self
     }
 
+    /// Get the expressions of state kind `kind`.
+    pub fn exprs(&self, kind: &str) -> &[StateExpr] {
+        self.exprs.get(kind).map_or(&[], Vec::as_slice)
+    }
+
+    /// Get the conditional effects of state kind `kind`.
+    pub fn conditionals(&self, kind: &str) -> &[Conditional] {
+        self.conditionals.get(kind).map_or(&[], Vec::as_slice)
+    }
+
     /// Extend the map with `metas`. Skips metas that have an state kind that
     /// isn't supported.
     pub fn extend_with_metas<'a, M>(&mut self, metas: M) -> Result<()>
@@ -37,14 +687,206 @@ impl Stateset {
     pub fn extend_with_meta(&mut self, meta: &Meta) -> Result<()> {
         let kind = meta.path().require_ident()?.to_string();
 
-        if let Some(states) = self.0.get_mut(&kind) {
+        // Counters are declared one per attribute, since they have their own options.
+        if kind == "counter"
+            && let Some(states) = self.states.get_mut(&kind)
+        {
+            states.push(State::from_counter_meta(meta)?);
+        } else if kind == "phases"
+            && let Some(states) = self.states.get_mut(&kind)
+        {
+            states.push(State::from_phases_meta(meta)?);
+        } else if kind == "alias"
+            && let Some(states) = self.states.get_mut(&kind)
+        {
+            meta.require_list()?.parse_nested_meta(|meta| {
+                states.push(State::from_alias_meta(&meta)?);
+                Ok(())
+            })?;
+        } else if kind == "copy"
+            && let Some(states) = self.states.get_mut(&kind)
+        {
+            meta.require_list()?.parse_nested_meta(|meta| {
+                states.push(State::from_copy_meta(&meta)?);
+                Ok(())
+            })?;
+        } else if kind == "when"
+            && let Some(conditionals) = self.conditionals.get_mut(&kind)
+        {
+            conditionals.push(Conditional::from_meta(meta)?);
+        } else if let Some(states) = self.states.get_mut(&kind) {
+            let exprs = self.exprs.get_mut(&kind).expect("kind is supported");
+            let mut at_least = Vec::new();
+
             meta.require_list()?.parse_nested_meta(|meta| {
-                let state = meta.path.require_ident().cloned()?;
-                states.push(state);
+                // Declared states can have substates, which are flattened after their parent.
+                if kind == "states" && !StateExpr::peek(&meta) {
+                    return State::parse_nested(&meta, None, states);
+                }
+
+                // Asserting a lower bound on the phase, like `at_least(Connected)`, is
+                // kept as its own state kind.
+                if kind == "assert" && meta.path.is_ident("at_least") && meta.input.peek(Paren) {
+                    meta.parse_nested_meta(|meta| {
+                        at_least.push(State::from_path(&meta.path)?);
+                        Ok(())
+                    })?;
+
+                    return Ok(());
+                }
+
+                match StateExpr::from_nested_meta(&meta)? {
+                    StateExpr::State(state) => states.push(state),
+                    expr => exprs.push(expr),
+                }
+
                 Ok(())
             })?;
+
+            if !at_least.is_empty() {
+                self.states
+                    .get_mut("at_least")
+                    .ok_or_else(|| Error::new(meta.span(), "invalid attribute"))?
+                    .extend(at_least);
+            }
         }
 
         Ok(())
     }
+
+    /// Replace the states that are `aliases` with their members. Skips members
+    /// that are already in the map, so aliases can overlap. Rejecting an alias
+    /// rejects all of its members being enabled together, rather than each of
+    /// them.
+    pub fn expand_aliases(&mut self, aliases: &[State]) {
+        for (kind, states) in self.states.iter_mut() {
+            let mut expanded: Vec<State> = Vec::new();
+
+            for state in states.drain(..) {
+                let alias = match find_alias(aliases, &state) {
+                    Some(alias) if kind == "reject" && alias.members.len() > 1 => {
+                        let members = alias.members.iter().cloned().map(StateExpr::State);
+                        let expr = StateExpr::All(members.collect());
+
+                        let exprs = self.exprs.get_mut(kind).expect("kind is supported");
+
+                        if !exprs.contains(&expr) {
+                            exprs.push(expr);
+                        }
+
+                        continue;
+                    }
+                    Some(alias) => alias,
+                    None => {
+                        expanded.push(state);
+                        continue;
+                    }
+                };
+
+                for member in &alias.members {
+                    if !expanded.contains(member) {
+                        expanded.push(member.clone());
+                    }
+                }
+            }
+
+            *states = expanded;
+        }
+
+        for expr in self.exprs.values_mut().flatten() {
+            expr.expand_aliases(aliases);
+        }
+
+        for conditional in self.conditionals.values_mut().flatten() {
+            conditional.expand_aliases(aliases);
+        }
+    }
+
+    /// Add the rules implied by the substates in `declared`. Asserting, presetting,
+    /// or assigning a substate implies its parent, while rejecting, deleting, or
+    /// toggling a parent implies its substates are disabled. Implied states that
+    /// are already covered by another rule are skipped.
+    pub fn expand_substates(&mut self, declared: &[State]) {
+        type Relatives = fn(&State, &[State]) -> Vec<State>;
+
+        let implications: [(&str, &str, Relatives, &[&str]); 6] = [
+            ("assert", "assert", ancestors, &[]),
+            ("preset", "preset", ancestors, &[]),
+            ("assign", "assign", ancestors, &["assert"]),
+            ("reject", "reject", descendants, &[]),
+            ("delete", "delete", descendants, &["reject"]),
+            ("toggle", "delete", descendants, &["reject"]),
+        ];
+
+        for (kind, implied_kind, relatives, covering_kinds) in implications {
+            let (Some(states), true) = (
+                self.states.get(kind),
+                self.states.contains_key(implied_kind),
+            ) else {
+                continue;
+            };
+
+            let implied = states
+                .iter()
+                .flat_map(|state| {
+                    relatives(state, declared).into_iter().map(|mut relative| {
+                        // Point errors about implied states at the rule that implies them.
+                        relative.ident.set_span(state.span());
+                        relative.ty = None;
+                        relative
+                    })
+                })
+                .collect_vec();
+
+            for state in implied {
+                let is_covered = [implied_kind]
+                    .iter()
+                    .chain(covering_kinds)
+                    .any(|kind| self.states[*kind].iter().any(|other| other.is_same(&state)));
+
+                if !is_covered {
+                    self.states
+                        .get_mut(implied_kind)
+                        .expect("kind is supported")
+                        .push(state);
+                }
+            }
+        }
+
+        for conditional in self.conditionals.values_mut().flatten() {
+            conditional.effects.expand_substates(declared);
+        }
+    }
+
+    /// Merge `other` into the map. Skips state kinds that aren't supported and
+    /// states, expressions, and conditional effects that are already in the map.
+    pub fn merge(&mut self, other: &Stateset) {
+        for (kind, conditionals) in self.conditionals.iter_mut() {
+            for conditional in other.conditionals(kind) {
+                if !conditionals.contains(conditional) {
+                    conditionals.push(conditional.clone());
+                }
+            }
+        }
+
+        for (kind, exprs) in self.exprs.iter_mut() {
+            for expr in other.exprs(kind) {
+                if !exprs.contains(expr) {
+                    exprs.push(expr.clone());
+                }
+            }
+        }
+
+        for (kind, states) in self.states.iter_mut() {
+            let Some(other_states) = other.get(kind) else {
+                continue;
+            };
+
+            for state in other_states {
+                if !states.contains(state) {
+                    states.push(state.clone());
+                }
+            }
+        }
+    }
 }
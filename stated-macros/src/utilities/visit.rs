@@ -1,7 +1,13 @@
+use itertools::Itertools;
+use proc_macro2::Span;
 use syn::{
-    Expr, ExprCall, ExprPath, ExprStruct, Member, Path, Type,
+    Expr, ExprCall, ExprPath, ExprStruct, GenericArgument, Ident, Item, Member, Pat, PatRest,
+    PatStruct, PatTupleStruct, Path, PathArguments, PathSegment, Receiver, Type, TypePath,
+    spanned::Spanned,
     visit_mut::{
-        VisitMut, visit_expr_call_mut, visit_expr_mut, visit_expr_struct_mut, visit_type_mut,
+        VisitMut, visit_expr_call_mut, visit_expr_mut, visit_expr_path_mut, visit_expr_struct_mut,
+        visit_pat_mut, visit_pat_struct_mut, visit_pat_tuple_struct_mut, visit_type_mut,
+        visit_type_path_mut,
     },
 };
 
@@ -20,6 +26,105 @@ impl VisitMut for ReplaceTypeInfer {
     }
 }
 
+pub struct ReplaceTypeInferInArgs {
+    pub ident: Ident,
+    pub replacements: Vec<(usize, Type)>,
+}
+
+impl VisitMut for ReplaceTypeInferInArgs {
+    fn visit_type_path_mut(&mut self, type_path: &mut TypePath) {
+        visit_type_path_mut(self, type_path);
+
+        // Check that the last segment of the path is the type being replaced in.
+        let Some(seg) = type_path.path.segments.last_mut() else {
+            return;
+        };
+
+        if seg.ident != self.ident {
+            return;
+        }
+
+        let PathArguments::AngleBracketed(args) = &mut seg.arguments else {
+            return;
+        };
+
+        // Only replace `_` in the arguments at the given indices.
+        for (index, ty) in self.replacements.iter() {
+            if let Some(GenericArgument::Type(arg_ty @ Type::Infer(_))) = args.args.get_mut(*index)
+            {
+                *arg_ty = ty.clone();
+            }
+        }
+    }
+}
+
+pub struct ReplaceTypeParam {
+    pub replacements: Vec<(Ident, Type)>,
+}
+
+impl VisitMut for ReplaceTypeParam {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        // Only a path that is just the parameter's ident names the parameter.
+        let replacement = match ty {
+            Type::Path(TypePath { qself: None, path }) => path.get_ident().and_then(|ident| {
+                self.replacements
+                    .iter()
+                    .find(|(param_ident, _)| param_ident == ident)
+            }),
+            _ => None,
+        };
+
+        match replacement {
+            Some((_, replacement)) => *ty = replacement.clone(),
+            None => visit_type_mut(self, ty),
+        }
+    }
+
+    // Nested items can't name the parameter, and may declare their own.
+    fn visit_item_mut(&mut self, _: &mut Item) {}
+}
+
+pub struct ReplaceSelfTy<'a> {
+    pub self_ty: &'a TypePath,
+}
+
+impl VisitMut for ReplaceSelfTy<'_> {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        visit_type_mut(self, ty);
+
+        let Type::Path(TypePath { qself: None, path }) = ty else {
+            return;
+        };
+
+        let [seg] = path.segments.iter().collect_vec()[..] else {
+            return;
+        };
+
+        if seg.ident != "Self" {
+            return;
+        }
+
+        // `Self` is the impl type, while `Self<..>` is the impl type with the
+        // given arguments.
+        let mut self_ty = self.self_ty.clone();
+
+        if let PathArguments::AngleBracketed(args) = &seg.arguments
+            && let Some(self_seg) = self_ty.path.segments.last_mut()
+        {
+            self_seg.arguments = PathArguments::AngleBracketed(args.clone());
+        }
+
+        *ty = Type::Path(self_ty);
+    }
+
+    // The receiver already is the impl type, and shorthand receivers can't name
+    // another type.
+    fn visit_receiver_mut(&mut self, _: &mut Receiver) {}
+
+    // Nested items have their own `Self`.
+    fn visit_item_mut(&mut self, _: &mut Item) {}
+}
+
 pub struct ReplaceExprInfer(pub Expr);
 
 impl VisitMut for ReplaceExprInfer {
@@ -33,6 +138,120 @@ impl VisitMut for ReplaceExprInfer {
     }
 }
 
+pub struct ReplaceSelfInStructConstruction<'a> {
+    pub path: &'a Path,
+    pub variants: Option<&'a [Ident]>,
+}
+
+impl ReplaceSelfInStructConstruction<'_> {
+    fn replace(&self, other: &mut Path) {
+        let mut segs = other.segments.iter();
+
+        if segs.next().is_none_or(|seg| seg.ident != "Self") {
+            return;
+        }
+
+        // `Self` names a struct, while `Self::Variant` names a variant of an enum.
+        let is_constructor = match (self.variants, segs.next()) {
+            (None, None) => true,
+            (Some(variants), Some(seg)) => segs.next().is_none() && variants.contains(&seg.ident),
+            _ => false,
+        };
+
+        if !is_constructor {
+            return;
+        }
+
+        // The type is named without arguments, so its states are inferred.
+        let rest = other.segments.iter().skip(1).cloned().collect_vec();
+        other.segments = self
+            .path
+            .segments
+            .iter()
+            .map(|seg| PathSegment::from(seg.ident.clone()))
+            .chain(rest)
+            .collect();
+    }
+}
+
+impl VisitMut for ReplaceSelfInStructConstruction<'_> {
+    // Constructing or matching a unit or tuple struct uses a path expression.
+    fn visit_expr_path_mut(&mut self, expr_path: &mut ExprPath) {
+        visit_expr_path_mut(self, expr_path);
+
+        if expr_path.qself.is_none() {
+            self.replace(&mut expr_path.path);
+        }
+    }
+
+    fn visit_expr_struct_mut(&mut self, expr_struct: &mut ExprStruct) {
+        visit_expr_struct_mut(self, expr_struct);
+
+        if expr_struct.qself.is_none() {
+            self.replace(&mut expr_struct.path);
+        }
+    }
+
+    fn visit_pat_tuple_struct_mut(&mut self, pat_tuple_struct: &mut PatTupleStruct) {
+        visit_pat_tuple_struct_mut(self, pat_tuple_struct);
+
+        if pat_tuple_struct.qself.is_none() {
+            self.replace(&mut pat_tuple_struct.path);
+        }
+    }
+
+    fn visit_pat_struct_mut(&mut self, pat_struct: &mut PatStruct) {
+        visit_pat_struct_mut(self, pat_struct);
+
+        if pat_struct.qself.is_none() {
+            self.replace(&mut pat_struct.path);
+        }
+    }
+
+    // Nested items have their own `Self`.
+    fn visit_item_mut(&mut self, _: &mut Item) {}
+}
+
+#[derive(Default)]
+pub struct FindExprInfer(pub Option<Span>);
+
+impl VisitMut for FindExprInfer {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        let Expr::Infer(expr_infer) = expr else {
+            visit_expr_mut(self, expr);
+            return;
+        };
+
+        self.0.get_or_insert(expr_infer.span());
+    }
+}
+
+pub struct FindTypeParam<'a> {
+    pub idents: &'a [Ident],
+    pub found: Option<Span>,
+}
+
+impl VisitMut for FindTypeParam<'_> {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        // Paths starting with the parameter's ident name it, or project from it.
+        // `Self` and macros might name it too.
+        let found = match ty {
+            Type::Path(TypePath { qself: None, path }) => path
+                .segments
+                .first()
+                .is_some_and(|seg| seg.ident == "Self" || self.idents.contains(&seg.ident)),
+            Type::Macro(_) => true,
+            _ => false,
+        };
+
+        if found {
+            self.found.get_or_insert(ty.span());
+        } else {
+            visit_type_mut(self, ty);
+        }
+    }
+}
+
 pub struct AddFieldInStructConstruction<'a> {
     pub path: &'a Path,
     pub field_member: Member,
@@ -99,8 +318,76 @@ impl VisitMut for AddFieldInStructConstruction<'_> {
     }
 }
 
+pub struct AddFieldInStructPattern<'a> {
+    pub path: &'a Path,
+}
+
+impl AddFieldInStructPattern<'_> {
+    fn should_modify(&self, other: &Path) -> bool {
+        self.path
+            .segments
+            .iter()
+            .map(|seg| &seg.ident)
+            .eq(other.segments.iter().map(|seg| &seg.ident))
+    }
+}
+
+impl VisitMut for AddFieldInStructPattern<'_> {
+    fn visit_pat_mut(&mut self, pat: &mut Pat) {
+        // Matching a unit struct is considered a path pattern. Since the pattern
+        // variant must be changed, capture it here.
+        let Pat::Path(pat_path) = pat else {
+            visit_pat_mut(self, pat);
+            return;
+        };
+
+        // Check that the path of the struct being matched is the impl type path.
+        if !self.should_modify(&pat_path.path) {
+            visit_pat_mut(self, pat);
+            return;
+        }
+
+        *pat = parse_squote!(#pat_path(_));
+    }
+
+    fn visit_pat_tuple_struct_mut(&mut self, pat_tuple_struct: &mut PatTupleStruct) {
+        visit_pat_tuple_struct_mut(self, pat_tuple_struct);
+
+        // Check that the path of the struct being matched is the impl type path.
+        if !self.should_modify(&pat_tuple_struct.path) {
+            return;
+        }
+
+        // Add a wildcard for the field. This is valid even after a rest pattern.
+        pat_tuple_struct.elems.push(parse_squote!(_));
+    }
+
+    fn visit_pat_struct_mut(&mut self, pat_struct: &mut PatStruct) {
+        visit_pat_struct_mut(self, pat_struct);
+
+        // Check that the path of the struct being matched is the impl type path.
+        if !self.should_modify(&pat_struct.path) {
+            return;
+        }
+
+        // Ignore the field with a rest pattern, unless there already is one.
+        if pat_struct.rest.is_none() {
+            if !pat_struct.fields.empty_or_trailing() {
+                pat_struct.fields.push_punct(Default::default());
+            }
+
+            pat_struct.rest = Some(PatRest {
+                attrs: Vec::new(),
+                dot2_token: Default::default(),
+            });
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use syn::ImplItemFn;
+
     use super::*;
 
     #[test]
@@ -133,6 +420,167 @@ mod tests {
         );
     }
 
+    #[test]
+    fn replace_type_infer_in_args_positional() {
+        let mut ty = parse_squote!(Result<Example<_, i32, _>, Other<_>>);
+
+        ReplaceTypeInferInArgs {
+            ident: parse_squote!(Example),
+            replacements: vec![(0, parse_squote!(First)), (2, parse_squote!(Second))],
+        }
+        .visit_type_mut(&mut ty);
+
+        assert_eq!(
+            ty,
+            parse_squote!(Result<Example<First, i32, Second>, Other<_>>)
+        );
+    }
+
+    #[test]
+    fn replace_type_param_nested() {
+        let mut ty = parse_squote!(Result<Example<S>, (S, T::S, Other<S::Assoc>)>);
+
+        ReplaceTypeParam {
+            replacements: vec![(parse_squote!(S), parse_squote!((Y, N)))],
+        }
+        .visit_type_mut(&mut ty);
+
+        assert_eq!(
+            ty,
+            parse_squote!(Result<Example<(Y, N)>, ((Y, N), T::S, Other<S::Assoc>)>)
+        );
+    }
+
+    #[test]
+    fn replace_type_param_in_block() {
+        let mut block = parse_squote! {{
+            let other: Example<S> = Example::<S>::new();
+            let _ = Vec::<A>::new();
+        }};
+
+        ReplaceTypeParam {
+            replacements: vec![
+                (parse_squote!(S), parse_squote!(Ingoing)),
+                (parse_squote!(A), parse_squote!(Other)),
+            ],
+        }
+        .visit_block_mut(&mut block);
+
+        assert_eq!(
+            block,
+            parse_squote! {{
+                let other: Example<Ingoing> = Example::<Ingoing>::new();
+                let _ = Vec::<Other>::new();
+            }}
+        );
+    }
+
+    #[test]
+    fn replace_self_ty_in_fn() {
+        let mut impl_item_fn: ImplItemFn = parse_squote! {
+            fn foo(self, other: Self) -> Result<Self<T, _>, Self::Error> {
+                let _: Vec<Self> = Vec::new();
+            }
+        };
+
+        ReplaceSelfTy {
+            self_ty: &parse_squote!(Example<T, S>),
+        }
+        .visit_impl_item_fn_mut(&mut impl_item_fn);
+
+        assert_eq!(
+            impl_item_fn,
+            parse_squote! {
+                fn foo(self, other: Example<T, S>) -> Result<Example<T, _>, Self::Error> {
+                    let _: Vec<Example<T, S> > = Vec::new();
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn replace_type_param_skips_nested_items() {
+        let mut block = parse_squote! {{
+            fn helper<S: Into<u32>>(s: S) -> u32 {
+                s.into()
+            }
+
+            let _: Option<S> = None;
+        }};
+
+        ReplaceTypeParam {
+            replacements: vec![(parse_squote!(S), parse_squote!((Y, N)))],
+        }
+        .visit_block_mut(&mut block);
+
+        assert_eq!(
+            block,
+            parse_squote! {{
+                fn helper<S: Into<u32>>(s: S) -> u32 {
+                    s.into()
+                }
+
+                let _: Option<(Y, N)> = None;
+            }}
+        );
+    }
+
+    #[test]
+    fn replace_self_in_struct_construction() {
+        let mut block = parse_squote! {{
+            let Self { x, .. } = self;
+            let _ = Self::new();
+            Self { x }
+        }};
+
+        ReplaceSelfInStructConstruction {
+            path: &parse_squote!(Example<S>),
+            variants: None,
+        }
+        .visit_block_mut(&mut block);
+
+        assert_eq!(
+            block,
+            parse_squote! {{
+                let Example { x, .. } = self;
+                let _ = Self::new();
+                Example { x }
+            }}
+        );
+    }
+
+    #[test]
+    fn replace_self_in_variant_construction() {
+        let mut block = parse_squote! {{
+            match self {
+                Self::Unit => Self::Unnamed(Self::DEFAULT),
+                Self::Unnamed(x) => Self::Named { x },
+                Self::Named { x } => Self::Unit,
+            }
+        }};
+
+        ReplaceSelfInStructConstruction {
+            path: &parse_squote!(Example<S>),
+            variants: Some(&[
+                parse_squote!(Unit),
+                parse_squote!(Unnamed),
+                parse_squote!(Named),
+            ]),
+        }
+        .visit_block_mut(&mut block);
+
+        assert_eq!(
+            block,
+            parse_squote! {{
+                match self {
+                    Example::Unit => Example::Unnamed(Self::DEFAULT),
+                    Example::Unnamed(x) => Example::Named { x },
+                    Example::Named { x } => Example::Unit,
+                }
+            }}
+        );
+    }
+
     #[test]
     fn replace_expr_infer_single_unnested() {
         let mut block = parse_squote! {{
@@ -236,6 +684,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn find_type_param_nested() {
+        let idents = [parse_squote!(S)];
+
+        for ty in [
+            parse_squote!(Vec<S>),
+            parse_squote!(<S as Pick>::T),
+            parse_squote!(Option<S::T>),
+            parse_squote!(Box<Self>),
+            parse_squote!(ty!()),
+        ] {
+            let mut finder = FindTypeParam {
+                idents: &idents,
+                found: None,
+            };
+            finder.visit_type_mut(&mut { ty });
+
+            assert!(finder.found.is_some());
+        }
+
+        for ty in [
+            parse_squote!(Vec<T>),
+            parse_squote!(module::S),
+            parse_squote!([u8; 4]),
+        ] {
+            let mut finder = FindTypeParam {
+                idents: &idents,
+                found: None,
+            };
+            finder.visit_type_mut(&mut { ty });
+
+            assert!(finder.found.is_none());
+        }
+    }
+
     #[test]
     fn add_field_in_struct_construction_unit_single_segment() {
         let mut block = parse_squote! {{
@@ -473,4 +956,92 @@ mod tests {
             }}
         );
     }
+
+    #[test]
+    fn add_field_in_struct_pattern_unit() {
+        let mut block = parse_squote! {{
+            let a::b::Struct = x;
+        }};
+
+        AddFieldInStructPattern {
+            path: &parse_squote!(a::b::Struct),
+        }
+        .visit_block_mut(&mut block);
+
+        assert_eq!(
+            block,
+            parse_squote! {{
+                let a::b::Struct(_) = x;
+            }}
+        );
+    }
+
+    #[test]
+    fn add_field_in_struct_pattern_unnamed() {
+        let mut block = parse_squote! {{
+            match x {
+                Struct(y) => {}
+                Struct(..) => {}
+            }
+        }};
+
+        AddFieldInStructPattern {
+            path: &parse_squote!(Struct),
+        }
+        .visit_block_mut(&mut block);
+
+        assert_eq!(
+            block,
+            parse_squote! {{
+                match x {
+                    Struct(y, _) => {}
+                    Struct(.., _) => {}
+                }
+            }}
+        );
+    }
+
+    #[test]
+    fn add_field_in_struct_pattern_named() {
+        let mut block = parse_squote! {{
+            match x {
+                Struct { y } => {}
+                Struct { y, .. } => {}
+            }
+        }};
+
+        AddFieldInStructPattern {
+            path: &parse_squote!(Struct),
+        }
+        .visit_block_mut(&mut block);
+
+        assert_eq!(
+            block,
+            parse_squote! {{
+                match x {
+                    Struct { y, .. } => {}
+                    Struct { y, .. } => {}
+                }
+            }}
+        );
+    }
+
+    #[test]
+    fn add_field_in_struct_pattern_nested() {
+        let mut block = parse_squote! {{
+            if let Some(Enum::Variant(y)) = x {}
+        }};
+
+        AddFieldInStructPattern {
+            path: &parse_squote!(Enum::Variant),
+        }
+        .visit_block_mut(&mut block);
+
+        assert_eq!(
+            block,
+            parse_squote! {{
+                if let Some(Enum::Variant(y, _)) = x {}
+            }}
+        );
+    }
 }
@@ -40,7 +40,8 @@
 //! your struct to represent its state. When you create an instance using a
 //! struct literal inside an associated function, Stated automatically fills in
 //! this field for you. For example, in `Example::new` only the `x` field is
-//! explicitly set. The phantom field is set behind the scenes.
+//! explicitly set. The phantom field is set behind the scenes. Similarly, when
+//! you match the struct with a pattern, the phantom field is ignored for you.
 //!
 //! ### Expansion
 //!
@@ -51,6 +52,39 @@
 //! }
 //! ```
 //!
+//! ### Enums
+//!
+//! Stated can also be used on enums. The phantom field is inserted into every
+//! variant, and unit variants are changed to tuple variants. Inside associated
+//! functions, Stated fills in the phantom field when constructing a variant and
+//! ignores it when matching a variant. Since every variant has a field, enums
+//! can't have explicit discriminants. Their fields also can't have accessors or
+//! depend on states.
+//!
+//! ```
+//! # {} /*
+//! #[stated(states(A, B, C))]
+//! enum Example<#[stated] S> {
+//!     Unit,
+//!     Unnamed(i32),
+//!     Named { x: i32 },
+//! }
+//!
+//! // Expands to:
+//!
+//! enum Example<S> {
+//!     Unit(::std::marker::PhantomData<S>),
+//!     Unnamed(i32, ::std::marker::PhantomData<S>),
+//!     Named { x: i32, __states: ::std::marker::PhantomData<S> },
+//! }
+//! # */
+//! ```
+//!
+//! ### Groups
+//!
+//! When [groups](super::tutorial::states#groups) are used, the phantom field
+//! tracks all designated parameters as a tuple, like `PhantomData<(C, A)>`.
+//!
 //! ### Limitation
 //!
 //! Outside of Stated associated functions, you'll need to [set the phantom
@@ -68,16 +102,77 @@
 //! ```
 //! # {} /*
 //! impl<S> Example<S> {
-//!     pub(crate) fn __reconstruct<__Re>(self) -> Example<__Re> {
-//!         Example {
-//!             x: self.x,
-//!             __states: ::std::marker::PhantomData,
+//!     pub(crate) const fn __reconstruct<__Re>(self) -> Example<__Re> {
+//!         let this = ::std::mem::ManuallyDrop::new(self);
+//!
+//!         unsafe {
+//!             let this = &*(&raw const this).cast::<Self>();
+//!
+//!             Example {
+//!                 x: ::std::ptr::read(&this.x),
+//!                 __states: ::std::marker::PhantomData,
+//!             }
 //!         }
 //!     }
 //! }
 //! # */
 //! ```
 //!
+//! The fields are read out of a `ManuallyDrop`, rather than moved out of
+//! `self`, so that types implementing `Drop` can be reconstructed. It's read
+//! through a pointer, so that the method can be const.
+//!
+//! ### Layout
+//!
+//! Reconstructing reads each field, which may copy large fields if the
+//! optimizer can't elide it. Structs declared with `repr_transparent_states`,
+//! like `#[stated(states(A, B), repr_transparent_states)]`, are `repr(C)`
+//! instead, so their layout is the same for every states type. They are
+//! reconstructed with a transmute, which checks the layouts match at compile
+//! time. Structs with this option can't have their own `repr` attribute, and
+//! their field types can't name the designated parameters, except through
+//! [state-dependent fields](super::tutorial::states#state-dependent-fields),
+//! since the field types must also match.
+//!
+//! ```
+//! # {} /*
+//! impl<S> Example<S> {
+//!     pub(crate) const fn __reconstruct<__Re>(self) -> Example<__Re> {
+//!         unsafe { ::stated::__transmute(self) }
+//!     }
+//! }
+//! # */
+//! ```
+//!
+//! ### Pointers
+//!
+//! Methods that take `self` in a `Box`, `Rc`, or `Arc` are reconstructed by
+//! `__reconstruct_box`, `__reconstruct_rc`, and `__reconstruct_arc` instead.
+//! The boxed variant reuses the allocation. The others return the pointer
+//! unchanged as an error if the value is shared.
+//!
+//! ```
+//! # {} /*
+//! impl<S> Example<S> {
+//!     pub(crate) fn __reconstruct_box<__Re>(self: Box<Self>) -> Box<Example<__Re>> {
+//!         ...
+//!     }
+//!
+//!     pub(crate) fn __reconstruct_rc<__Re>(
+//!         self: Rc<Self>,
+//!     ) -> Result<Rc<Example<__Re>>, Rc<Self>> {
+//!         Rc::try_unwrap(self).map(|this| Rc::new(this.__reconstruct()))
+//!     }
+//!
+//!     pub(crate) fn __reconstruct_arc<__Re>(
+//!         self: Arc<Self>,
+//!     ) -> Result<Arc<Example<__Re>>, Arc<Self>> {
+//!         Arc::try_unwrap(self).map(|this| Arc::new(this.__reconstruct()))
+//!     }
+//! }
+//! # */
+//! ```
+//!
 //! ## Token Export Macro
 //!
 //! A private macro is defined to export all tokens from the struct definition
@@ -24,16 +24,19 @@
 //! The complex generics used by the typestate pattern often lead to confusing
 //! compiler errors. Stated does not address this limitation.
 //!
-//! # No Self Type
+//! # Ingoing Self Type
 //!
 //! The typestate pattern doesn't work well with `Self`. This is due to generic
-//! state changes altering the type that `Self` refers to. Stated does not
-//! attempt to fix this.
+//! state changes altering the type that `Self` refers to. Stated
+//! [understands `Self`](super::tutorial::syntax#self), but a bare `Self` is
+//! still the ingoing type, so returning a changed state must use `Self<_>`.
 //!
 //! # Instantiating Outside
 //!
 //! Stated adds a [phantom field](super::expansion#phantom-field) to the struct.
 //! Outside of Stated associated functions, you must specify the field yourself.
+//! The same applies to every variant of an enum, which must also be matched
+//! with the phantom field in mind (e.g. `Example::Unnamed(x, ..)`).
 //!
 //! In the code below, `Example` must have the phantom field explicitly set in
 //! `main`, which isn't a Stated associated function.
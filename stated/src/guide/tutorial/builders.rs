@@ -0,0 +1,45 @@
+//! How to derive builders.
+//!
+//! # Derived Builders
+//!
+//! Structs with named fields can derive [`StatedBuilder`](stated::StatedBuilder),
+//! which generates a builder named after the struct with a `Builder` suffix. The
+//! builder is declared and implemented with Stated, like any other struct.
+//!
+//! Every required field has a state named after it with a `Has` prefix, like
+//! `HasBody` for field `body`. The setter of a required field rejects and assigns
+//! its state, so it can only be set once. `build` asserts the states of every
+//! required field.
+//!
+//! Fields with `#[builder(default)]` are optional, and start with their default
+//! value. They can also start with a given value, like
+//! `#[builder(default = 3)]`. Optional fields don't have a state, so their
+//! setters can be called any number of times. If every field is optional, there
+//! are no states to track, so the builder is a plain struct that isn't
+//! declared with Stated.
+//!
+//! The builder is created with `new`, or with `builder` on the struct. Its
+//! designated parameter defaults to [`Initial`](super::syntax#initial), so the
+//! builder named without states, like `MessageBuilder`, is a new builder.
+//!
+//! ## Example
+//!
+//! In the code below, `MessageBuilder::build` can only be called once the
+//! recipients and body are set.
+//!
+//! ```
+//! # {} /*
+//! #[derive(StatedBuilder)]
+//! struct Message {
+//!     recipients: Vec<String>,
+//!     body: String,
+//!     #[builder(default)]
+//!     subject: Option<String>,
+//! }
+//!
+//! let message = Message::builder()
+//!     .recipients(vec!["alice".to_owned()])
+//!     .body("Hello".to_owned())
+//!     .build();
+//! # */
+//! ```
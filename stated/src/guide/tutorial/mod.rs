@@ -1,4 +1,6 @@
 //! How to use Stated.
 
+pub mod builders;
 pub mod states;
 pub mod syntax;
+pub mod traits;
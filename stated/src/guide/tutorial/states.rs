@@ -99,3 +99,473 @@
 //! }
 //! # */
 //! ```
+//!
+//! # Groups
+//!
+//! States can be split into groups, with each group tracked by its own
+//! designated parameter. Name the group in the designating attribute, like
+//! `#[stated(conn)]`, and qualify its states with the group name, like
+//! `conn::Open`. Every group must be designated on the impl block, and state
+//! names must be unique across groups.
+//!
+//! In a return type, each `_` is replaced with the states of the group whose
+//! parameter it stands in for.
+//!
+//! ## Example
+//!
+//! In the code below, the connection and authentication states are tracked by
+//! `C` and `A` respectively. `Example::elevate` requires the connection to be
+//! open and the user to be logged in.
+//!
+//! ```
+//! # {} /*
+//! #[stated(states(conn::Open, auth::LoggedIn, auth::Admin))]
+//! struct Example<#[stated(conn)] C, #[stated(auth)] A> {
+//!     ...
+//! }
+//!
+//! #[stated]
+//! impl<#[stated(conn)] C, #[stated(auth)] A> Example<C, A> {
+//!     #[stated(assert(conn::Open, auth::LoggedIn), assign(auth::Admin))]
+//!     fn elevate(self) -> Example<_, _> {
+//!         ...
+//!     }
+//! }
+//! # */
+//! ```
+//!
+//! # Exclusive Groups
+//!
+//! When exactly one of several states should be enabled at a time, declare an
+//! exclusive group with the `group` attribute, like
+//! `group(Phase = Idle | Running | Stopped)`. Rules select a choice of the
+//! group, like `assert(Phase = Running)` or `assign(Phase = Stopped)`. Since a
+//! choice is always enabled, exclusive groups can't be rejected or deleted.
+//!
+//! An exclusive group takes a single slot in the states type, holding
+//! [`Choice`](`stated::Choice`) with the position of the enabled choice. It
+//! starts at its first choice when constructed, unless it is preset to another,
+//! like `preset(Phase = Running)`.
+//!
+//! ## Example
+//!
+//! In the code below, `Example::new` starts out `Idle`. `Example::stop` can
+//! only be called once `Example::start` has transitioned it to `Running`.
+//!
+//! ```
+//! # {} /*
+//! #[stated(group(Phase = Idle | Running | Stopped))]
+//! struct Example<#[stated] S> {
+//!     ...
+//! }
+//!
+//! #[stated]
+//! impl<#[stated] S> Example<S> {
+//!     #[stated]
+//!     fn new() -> Example<_> {
+//!         ...
+//!     }
+//!
+//!     #[stated(assert(Phase = Idle), assign(Phase = Running))]
+//!     fn start(self) -> Example<_> {
+//!         ...
+//!     }
+//!
+//!     #[stated(assert(Phase = Running), assign(Phase = Stopped))]
+//!     fn stop(self) -> Example<_> {
+//!         ...
+//!     }
+//! }
+//! # */
+//! ```
+//!
+//! # Counters
+//!
+//! Counters track how many times something happened. Declare one per `counter`
+//! attribute, like `counter(Retries, max = 3)`. The max is optional, but
+//! without it a counter can only be bounded from below or to an exact count.
+//!
+//! Use the `increment` attribute to add one to a counter, and bound it with
+//! `assert`, like `assert(Retries >= 2)`. The comparisons `==`, `<`, `<=`, `>`,
+//! and `>=` are supported. A counter can't be incremented past its max.
+//!
+//! Counters start at zero when constructed and are counted with the type-level
+//! naturals [`Zero`](`stated::Zero`) and [`Succ`](`stated::Succ`).
+//!
+//! ## Example
+//!
+//! In the code below, `Example::retry` can be called at most three times, and
+//! `Example::submit` requires `Example::sign` to be called at least twice.
+//!
+//! ```
+//! # {} /*
+//! #[stated(counter(Retries, max = 3), counter(Signatures))]
+//! struct Example<#[stated] S> {
+//!     ...
+//! }
+//!
+//! #[stated]
+//! impl<#[stated] S> Example<S> {
+//!     #[stated(increment(Retries))]
+//!     fn retry(self) -> Example<_> {
+//!         ...
+//!     }
+//!
+//!     #[stated(increment(Signatures))]
+//!     fn sign(self) -> Example<_> {
+//!         ...
+//!     }
+//!
+//!     #[stated(assert(Signatures >= 2))]
+//!     fn submit(self) {
+//!         ...
+//!     }
+//! }
+//! # */
+//! ```
+//!
+//! # Expressions
+//!
+//! Besides single states, `assert` and `reject` accept expressions of states
+//! built with `any(...)`, `all(...)`, and `not(...)`. Expressions are checked
+//! with trait bounds on the states, using [`Bit`](`stated::Bit`) and
+//! [`True`](`stated::True`), rather than by substituting
+//! [`Y`](`stated::Y`) and [`N`](`stated::N`), so a single associated function
+//! covers every combination that satisfies them.
+//!
+//! ## Example
+//!
+//! In the code below, `Example::verify` can be called if either state
+//! `HasEmail` or `HasPhone` is enabled, including when both are.
+//!
+//! ```
+//! # {} /*
+//! #[stated]
+//! impl<#[stated] S> Example<S> {
+//!     #[stated(assert(any(HasEmail, HasPhone)), assign(Verified))]
+//!     fn verify(self) -> Example<_> {
+//!         ...
+//!     }
+//! }
+//! # */
+//! ```
+//!
+//! # Aliases
+//!
+//! Aliases name a combination of states with `alias(Name = A + B + ...)`, and
+//! can be used in place of states in `assert`, `reject`, `assign`, `delete`,
+//! and `preset`. Asserting an alias asserts all of its members, while rejecting
+//! an alias rejects all of its members being enabled together.
+//!
+//! A public type alias is also generated for each alias, named after the type
+//! and the alias, with the members enabled and the other states left as
+//! generic parameters.
+//!
+//! ## Example
+//!
+//! In the code below, `Example::send` can be called once states `HasRecipient`,
+//! `HasSubject`, and `HasBody` are enabled, and `ExampleComplete` names the
+//! type with all three enabled.
+//!
+//! ```
+//! # {} /*
+//! #[stated(
+//!     states(HasRecipient, HasSubject, HasBody),
+//!     alias(Complete = HasRecipient + HasSubject + HasBody)
+//! )]
+//! struct Example<#[stated] S> {
+//!     ...
+//! }
+//!
+//! #[stated]
+//! impl<#[stated] S> Example<S> {
+//!     #[stated(assert(Complete))]
+//!     fn send(self) {
+//!         ...
+//!     }
+//! }
+//!
+//! fn send_later(example: ExampleComplete) {
+//!     ...
+//! }
+//! # */
+//! ```
+//!
+//! # Conditional Effects
+//!
+//! Methods can transition states depending on the ingoing states with
+//! `when(Condition => assign(...), delete(...))`. The condition is a state or an
+//! expression, and multiple conditions separated by commas must all hold. If the
+//! condition doesn't hold, the states are left unchanged.
+//!
+//! Conditional effects are computed with [`Bit::Select`](`stated::Bit::Select`),
+//! so a single associated function covers both outcomes.
+//!
+//! ## Example
+//!
+//! In the code below, `Example::flush` assigns state `Clean` only if state
+//! `Buffered` is enabled.
+//!
+//! ```
+//! # {} /*
+//! #[stated]
+//! impl<#[stated] S> Example<S> {
+//!     #[stated(delete(Buffered), when(Buffered => assign(Clean)))]
+//!     fn flush(self) -> Example<_> {
+//!         ...
+//!     }
+//! }
+//! # */
+//! ```
+//!
+//! # Toggles and Copies
+//!
+//! Besides assigning and deleting, methods can flip a state with `toggle(...)`,
+//! or set a state to the ingoing value of another with `copy(A = B)`. Both stay
+//! generic over the ingoing states, and are computed with
+//! [`Bit`](`stated::Bit`). A state can only be transitioned once per ruleset.
+//!
+//! ## Example
+//!
+//! In the code below, `Example::switch` flips state `Open`, and `Example::sync`
+//! sets state `Dirty` to whether state `Modified` is enabled.
+//!
+//! ```
+//! # {} /*
+//! #[stated]
+//! impl<#[stated] S> Example<S> {
+//!     #[stated(toggle(Open))]
+//!     fn switch(self) -> Example<_> {
+//!         ...
+//!     }
+//!
+//!     #[stated(copy(Dirty = Modified))]
+//!     fn sync(self) -> Example<_> {
+//!         ...
+//!     }
+//! }
+//! # */
+//! ```
+//!
+//! # Phases
+//!
+//! Lifecycles that only move forward are declared as ordered phases, like
+//! `phases(Created, Connected, Closed)`. Phases start at the first phase, or the
+//! preset phase, and are moved forward with `advance(...)`. A phase can be
+//! asserted exactly, like `assert(Connected)`, or as a lower bound, like
+//! `assert(at_least(Connected))`.
+//!
+//! Advancing is only possible from the phase advanced to or an earlier one, so
+//! going backwards doesn't compile. Like counters, phases are tracked with the
+//! type-level naturals [`Zero`](`stated::Zero`) and [`Succ`](`stated::Succ`),
+//! and the phases type is named `Phase` in the documentation.
+//!
+//! ## Example
+//!
+//! In the code below, `Example::send` can be called once `Example::connect` is
+//! called, including after `Example::close` is, but `Example::connect` can't be
+//! called after `Example::close` is.
+//!
+//! ```
+//! # {} /*
+//! #[stated(phases(Created, Connected, Closed))]
+//! struct Example<#[stated] S> {
+//!     ...
+//! }
+//!
+//! #[stated]
+//! impl<#[stated] S> Example<S> {
+//!     #[stated(advance(Connected))]
+//!     fn connect(self) -> Example<_> {
+//!         ...
+//!     }
+//!
+//!     #[stated(advance(Closed))]
+//!     fn close(self) -> Example<_> {
+//!         ...
+//!     }
+//!
+//!     #[stated(assert(at_least(Connected)))]
+//!     fn send(&self) {
+//!         ...
+//!     }
+//! }
+//! # */
+//! ```
+//!
+//! # Substates
+//!
+//! States can be nested, like `states(Connected { Authenticated, Encrypted })`,
+//! so a substate is only enabled while its parent is. Asserting, assigning, or
+//! presetting a substate implies its parent, while rejecting, deleting, or
+//! toggling a parent implies its substates are disabled. Substates can't be
+//! toggled, and nested states can't be copied.
+//!
+//! ## Example
+//!
+//! In the code below, `Example::authenticate` also enables state `Connected`,
+//! and `Example::close` also disables state `Authenticated`.
+//!
+//! ```
+//! # {} /*
+//! #[stated(states(Connected { Authenticated }))]
+//! struct Example<#[stated] S> {
+//!     ...
+//! }
+//!
+//! #[stated]
+//! impl<#[stated] S> Example<S> {
+//!     #[stated(assign(Authenticated))]
+//!     fn authenticate(self) -> Example<_> {
+//!         ...
+//!     }
+//!
+//!     #[stated(delete(Connected))]
+//!     fn close(self) -> Example<_> {
+//!         ...
+//!     }
+//! }
+//! # */
+//! ```
+//!
+//! # Parameterized States
+//!
+//! States declared with a type parameter, like `states(Encoding<E>)`, carry a
+//! type while enabled. They are assigned with a type, like
+//! `assign(Encoding<Gzip>)`, and asserted either with a type, or with `_` to
+//! keep the carried type generic, like `assert(Encoding<_>)`. The type parameter
+//! then names the carried type in the associated function. Enabled
+//! parameterized states are [`With`](`stated::With`) the carried type.
+//!
+//! Parameterized states can be rejected and deleted like other states, but they
+//! can't be toggled, copied, or used in expressions and conditional effects.
+//!
+//! ## Example
+//!
+//! In the code below, `Example::build` uses the codec carried by state
+//! `Encoding`.
+//!
+//! ```
+//! # {} /*
+//! #[stated(states(Encoding<E>))]
+//! struct Example<#[stated] S> {
+//!     ...
+//! }
+//!
+//! #[stated]
+//! impl<#[stated] S> Example<S> {
+//!     #[stated(assign(Encoding<Gzip>))]
+//!     fn gzip(self) -> Example<_> {
+//!         ...
+//!     }
+//!
+//!     #[stated(assert(Encoding<_>))]
+//!     fn build(self) -> Vec<u8>
+//!     where
+//!         E: Codec,
+//!     {
+//!         ...
+//!     }
+//! }
+//! # */
+//! ```
+//!
+//! # State-Dependent Fields
+//!
+//! Fields can declare their type per state, like
+//! `#[stated(when(HasBody) = String, otherwise = ())]`. The field is a `String`
+//! while state `HasBody` is enabled and `()` otherwise, which is also the default
+//! when `otherwise` is left out. Only states that are either enabled or disabled
+//! can be depended on. The type written on the field is only a placeholder,
+//! which is replaced, so it must be `_` or `()`.
+//!
+//! Since `_` moves the fields as is, it can only be used if the states the
+//! fields depend on are unchanged. Associated functions that transition those
+//! states construct the struct instead. Outside of impl blocks, the states that
+//! fields depend on must be known or bound by [`Bit`](stated::Bit).
+//!
+//! ## Example
+//!
+//! In the code below, `Example::build` reads the body as a `String`.
+//!
+//! ```
+//! # {} /*
+//! #[stated(states(HasBody))]
+//! struct Example<#[stated] S> {
+//!     #[stated(when(HasBody) = String)]
+//!     body: (),
+//! }
+//!
+//! #[stated]
+//! impl<#[stated] S> Example<S> {
+//!     #[stated(assign(HasBody))]
+//!     fn body(self, body: String) -> Example<_> {
+//!         Example { body }
+//!     }
+//!
+//!     #[stated(assert(HasBody))]
+//!     fn build(self) -> String {
+//!         self.body
+//!     }
+//! }
+//! # */
+//! ```
+//!
+//! # Borrowed Methods
+//!
+//! Methods can take `&self` or `&mut self`. A reference receiver can't be
+//! reconstructed, so these methods can only assert and reject states, and can't
+//! use the [inferred expression](super::syntax#infer).
+//!
+//! Read-only methods can be marked with `borrow`, like
+//! `#[stated(borrow, assert(A))]`, which requires them to take `&self`. It's
+//! only a checked marker and doesn't change the expansion. Like any other
+//! method, borrowed methods are generic over every state their ruleset doesn't
+//! mention, so they can be called in any of those states.
+//!
+//! ## Example
+//!
+//! In the code below, `Example::len` can be called in any state, while
+//! `Example::push` can only be called while state `A` is disabled.
+//!
+//! ```
+//! # {} /*
+//! #[stated]
+//! impl<#[stated] S> Example<S> {
+//!     #[stated(borrow)]
+//!     fn len(&self) -> usize {
+//!         ...
+//!     }
+//!
+//!     #[stated(reject(A))]
+//!     fn push(&mut self, item: Item) {
+//!         ...
+//!     }
+//! }
+//! # */
+//! ```
+//!
+//! # Accessors
+//!
+//! Fields can generate accessors that are restricted by a ruleset, like
+//! `#[stated(get, assert(HasRecipient))]`. `get` generates a method named after
+//! the field that returns a reference, and `get_mut` generates one suffixed with
+//! `_mut` that returns a mutable reference. Accessors only borrow the struct, so
+//! their rulesets can only assert and reject states, and `get` is a
+//! [borrowed method](#borrowed-methods). Accessors of state-dependent fields
+//! must assert or reject the state the field depends on.
+//!
+//! ## Example
+//!
+//! In the code below, `Example::recipients` can only be called once state
+//! `HasRecipient` is enabled.
+//!
+//! ```
+//! # {} /*
+//! #[stated(states(HasRecipient))]
+//! struct Example<#[stated] S> {
+//!     #[stated(get, assert(HasRecipient))]
+//!     recipients: Vec<String>,
+//! }
+//! # */
+//! ```
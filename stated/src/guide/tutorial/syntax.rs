@@ -7,6 +7,10 @@
 //! designated on struct definitions are used to track states. Parameters
 //! designated on impl blocks are replaced by generic parameters used by Stated.
 //!
+//! Bounds on a designated parameter, whether inline or in the where clause,
+//! apply to the state types that replace it. On impl blocks, a function can
+//! only be called if both its ingoing and outgoing state types satisfy them.
+//!
 //! ## Example
 //!
 //! In the code below, generic parameter `U` is designated.
@@ -25,6 +29,30 @@
 //! # */
 //! ```
 //!
+//! # Initial
+//!
+//! The designated parameter on a struct or enum definition can default to
+//! `Initial`, which is replaced with the states type that only has the
+//! [preset](super::states#declare-and-preset) states enabled. The type can then
+//! be named without its states, like the type of a field holding a new value.
+//!
+//! ## Example
+//!
+//! In the code below, `Example` names the type with only state `A` enabled.
+//!
+//! ```
+//! # {} /*
+//! #[stated(states(A, B), preset(A))]
+//! struct Example<#[stated] S = Initial> {
+//!     ...
+//! }
+//!
+//! struct Holder {
+//!     example: Example,
+//! }
+//! # */
+//! ```
+//!
 //! # Infer
 //!
 //! In the return type of an associated function, the inferred type (`_`) is
@@ -32,6 +60,16 @@
 //!
 //! In the body of a method, the inferred expression (`_`)
 //! [reconstructs](#reconstruct-method) `self` with the outgoing state type.
+//! If `self` is in a `Box`, like `self: Box<Self>`, it's reconstructed in the
+//! same allocation. If it's in an `Rc` or `Arc`, it's only reconstructed if it
+//! isn't shared, so the inferred expression is a `Result` that holds the
+//! unchanged pointer as an error.
+//!
+//! Reconstructing `self` moves its fields into the new value without dropping
+//! it, so a type that implements `Drop` isn't dropped by a transition. It's
+//! dropped once, when the value with the final states goes out of scope, and
+//! since its `Drop` impl must be generic over the states, the same impl runs
+//! for every states type.
 //!
 //! ## Example
 //!
@@ -59,6 +97,122 @@
 //!         ...
 //!         Ok(_)
 //!     }
+//!
+//!     #[stated(...)]
+//!     fn baz(self: Box<Self>) -> Box<Example<T, _, V>> {
+//!         _
+//!     }
+//!
+//!     #[stated(...)]
+//!     fn qux(self: Rc<Self>) -> Result<Rc<Example<T, _, V>>, Rc<Self>> {
+//!         _
+//!     }
 //! }
 //! # */
 //! ```
+//!
+//! # Self
+//!
+//! In associated functions, `Self` names the type of the impl block. In methods,
+//! it's the ingoing state type, and in constructors, it's the constructed state
+//! type. `Self` can also be given arguments, like `Self<T, _>`, so the
+//! [inferred type](#infer) can be used with it.
+//!
+//! Constructing or matching `Self { .. }`, `Self(..)`, or `Self::Variant(..)`
+//! fills in the [phantom field](super::super::expansion#phantom-field) like the
+//! type's own name does, so the constructed states are inferred.
+//!
+//! ## Example
+//!
+//! In the code below, `Example::new` returns the constructed state type, while
+//! `Example::foo` returns the outgoing state type.
+//!
+//! ```
+//! # {} /*
+//! #[stated]
+//! impl<T, #[stated] U, V> Example<T, U, V> {
+//!     #[stated(...)]
+//!     fn new(x: T) -> Self {
+//!         Self { x }
+//!     }
+//!
+//!     #[stated(...)]
+//!     fn foo(self) -> Self<T, _, V> {
+//!         _
+//!     }
+//! }
+//! # */
+//! ```
+//!
+//! # Ingoing
+//!
+//! In methods, the designated parameter is replaced with the ingoing state type
+//! wherever it's named, including the argument types, the return type, and the
+//! body. This makes it possible to return `self` unchanged, or to take other
+//! values of the same state type. In constructors, it's replaced with the
+//! constructed state type instead. Items nested in the body, like helper
+//! functions, are left as is, since they may declare their own parameter with
+//! the same name.
+//!
+//! ## Example
+//!
+//! In the code below, `Example::try_finish` returns `self` unchanged if it
+//! can't finish, and `Example::merge` takes another `Example` with the same
+//! states.
+//!
+//! ```
+//! # {} /*
+//! #[stated]
+//! impl<T, #[stated] U, V> Example<T, U, V> {
+//!     #[stated(...)]
+//!     fn try_finish(self) -> Result<Finished, Example<T, U, V>> {
+//!         if ... {
+//!             return Err(self);
+//!         }
+//!
+//!         ...
+//!     }
+//!
+//!     #[stated(...)]
+//!     fn merge(self, other: Example<T, U, V>) -> Example<T, _, V> {
+//!         ...
+//!     }
+//! }
+//! # */
+//! ```
+//!
+//! # Const
+//!
+//! Stated associated functions can be `const`. The [inferred
+//! expression](#infer) can be used in a const method that takes `self` by
+//! value, but not in one that takes it in a pointer, since pointers can't be
+//! allocated in a const fn.
+//!
+//! Reconstructing `self` doesn't drop it, so types with destructors can be
+//! transitioned in a const fn. Their fields still can't be dropped in one,
+//! like when they're overwritten, as with any const fn.
+//!
+//! ## Example
+//!
+//! In the code below, `CONFIG` is built at compile time. Like any const item,
+//! its type must be written out, since the inferred type can't be used there.
+//!
+//! ```
+//! # {} /*
+//! #[stated]
+//! impl<#[stated] S> Config<S> {
+//!     #[stated]
+//!     const fn new() -> Config<_> {
+//!         Config { port: 0 }
+//!     }
+//!
+//!     #[stated(assign(Port))]
+//!     const fn with_port(mut self, port: u16) -> Config<_> {
+//!         self.port = port;
+//!         _
+//!     }
+//! }
+//!
+//! const CONFIG: Config<Y> = Config::new().with_port(80);
+//! # */
+//! ```
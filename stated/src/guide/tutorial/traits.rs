@@ -0,0 +1,71 @@
+//! How to implement traits.
+//!
+//! # Trait Impls
+//!
+//! Traits can be implemented with Stated the same way as inherent impls. The
+//! methods of a trait impl can have a ruleset, but unlike inherent impls, a
+//! ruleset is optional and at most one can be specified per method.
+//!
+//! A type can only implement a trait once, so a trait impl is expanded into a
+//! single impl block. The rulesets of all its methods are merged. The trait is
+//! only implemented for states satisfying every asserted and rejected state.
+//! Since the outgoing state type is shared by the whole impl block, only one
+//! method can transition states. Rulesets that conflict once merged are
+//! rejected.
+//!
+//! The inferred type (`_`) is replaced with the outgoing state type in the
+//! return types of methods, as well as in associated types and constants.
+//!
+//! ## Example
+//!
+//! In the code below, `Iterator` is only implemented for `Example` when state
+//! `A` is enabled. `Finish` is only implemented when state `B` is disabled, and
+//! finishing transitions state `B` to enabled.
+//!
+//! ```
+//! # {} /*
+//! #[stated]
+//! impl<#[stated] S> Iterator for Example<S> {
+//!     type Item = i32;
+//!
+//!     #[stated(assert(A))]
+//!     fn next(&mut self) -> Option<Self::Item> {
+//!         ...
+//!     }
+//! }
+//!
+//! #[stated]
+//! impl<#[stated] S> Finish for Example<S> {
+//!     type Output = Example<_>;
+//!
+//!     #[stated(reject(B), assign(B))]
+//!     fn finish(self) -> Self::Output {
+//!         _
+//!     }
+//! }
+//! # */
+//! ```
+//!
+//! # Impl Rulesets
+//!
+//! A ruleset can be specified on the impl block itself to only implement a
+//! trait for certain states. Impl rulesets can only assert or reject states.
+//!
+//! An impl ruleset can also be specified on an inherent impl, where it is
+//! merged into the ruleset of every method in the impl block.
+//!
+//! ## Example
+//!
+//! In the code below, `Display` is only implemented for `Example` when state
+//! `A` is enabled and state `C` is disabled.
+//!
+//! ```
+//! # {} /*
+//! #[stated(assert(A), reject(C))]
+//! impl<#[stated] S> Display for Example<S> {
+//!     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+//!         ...
+//!     }
+//! }
+//! # */
+//! ```
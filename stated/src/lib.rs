@@ -4,7 +4,9 @@
 
 extern crate self as stated;
 
-pub use stated_macros::stated;
+use std::{marker::PhantomData, mem::ManuallyDrop};
+
+pub use stated_macros::{StatedBuilder, stated};
 
 #[doc(hidden)]
 pub use stated_macros::stated_internal;
@@ -13,12 +15,105 @@ pub use stated_macros::stated_internal;
 pub mod guide;
 
 /// Indicates a disabled state.
-#[derive(Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct N;
 
 /// Indicates an enabled state.
-#[derive(Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Y;
 
+/// A state that is either [enabled](Y) or [disabled](N), used to evaluate
+/// expressions of states and to compute transitions from the ingoing states.
+pub trait Bit {
+    /// The opposite state.
+    type Not: Bit;
+
+    /// Enabled if both states are enabled.
+    type And<B: Bit>: Bit;
+
+    /// Enabled if either state is enabled.
+    type Or<B: Bit>: Bit;
+
+    /// `T` if the state is enabled, otherwise `F`.
+    type Select<T, F>;
+}
+
+impl Bit for N {
+    type Not = Y;
+    type And<B: Bit> = N;
+    type Or<B: Bit> = B;
+    type Select<T, F> = F;
+}
+
+impl Bit for Y {
+    type Not = N;
+    type And<B: Bit> = B;
+    type Or<B: Bit> = Y;
+    type Select<T, F> = T;
+}
+
+/// Implemented only for [enabled](Y) states, used to require an expression of
+/// states to be enabled.
+pub trait True {}
+
+impl True for Y {}
+
+/// Indicates the enabled state of an exclusive group, by its position in the
+/// group's declaration.
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Choice<const I: usize>;
+
+/// Indicates an enabled state that carries the type `T`, used for states
+/// declared with a type parameter.
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct With<T>(PhantomData<T>);
+
+/// The natural number zero, used as the count of a counter.
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Zero;
+
+/// The natural number after `T`, used as the count of a counter.
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Succ<T>(PhantomData<T>);
+
 /// Placeholder for a stateless type.
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct __;
+
+/// The placeholder is a disabled state, so field types that depend on a state
+/// are known for stateless types.
+impl Bit for __ {
+    type Not = Y;
+    type And<B: Bit> = N;
+    type Or<B: Bit> = B;
+    type Select<T, F> = F;
+}
+
+/// Transmute `from` into `B`. The types must have the same size and alignment,
+/// which is checked when the function is instantiated.
+///
+/// # Safety
+///
+/// `from` must be a valid value of `B`.
+#[doc(hidden)]
+pub const unsafe fn __transmute<A, B>(from: A) -> B {
+    const {
+        assert!(
+            size_of::<A>() == size_of::<B>() && align_of::<A>() == align_of::<B>(),
+            "the states types have different layouts",
+        );
+    }
+
+    #[repr(C)]
+    union Transmute<A, B> {
+        from: ManuallyDrop<A>,
+        to: ManuallyDrop<B>,
+    }
+
+    let transmute = Transmute {
+        from: ManuallyDrop::new(from),
+    };
+
+    // SAFETY: The caller guarantees `from` is a valid value of `B`.
+    ManuallyDrop::into_inner(unsafe { transmute.to })
+}
@@ -0,0 +1,21 @@
+use stated::stated;
+
+#[stated(states(HasRecipient))]
+pub struct Test<#[stated] S> {
+    #[stated(get, assert(HasRecipient))]
+    recipients: Vec<String>,
+}
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test {
+            recipients: Vec::new(),
+        }
+    }
+}
+
+fn main() {
+    Test::new().recipients();
+}
@@ -0,0 +1,24 @@
+use stated::stated;
+
+#[stated(states(A, B), alias(Both = A + B))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(assign(A))]
+    pub fn a(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assert(Both))]
+    pub fn both(&self) {}
+}
+
+fn main() {
+    Test::new().a().both();
+}
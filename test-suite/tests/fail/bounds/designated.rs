@@ -0,0 +1,16 @@
+use std::fmt::Display;
+
+use stated::stated;
+
+#[stated(states(A))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S: Display> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+}
+
+fn main() {}
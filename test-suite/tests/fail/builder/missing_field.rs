@@ -0,0 +1,11 @@
+use stated::StatedBuilder;
+
+#[derive(StatedBuilder)]
+pub struct Message {
+    recipients: Vec<String>,
+    body: String,
+}
+
+fn main() {
+    MessageBuilder::new().body(String::new()).build();
+}
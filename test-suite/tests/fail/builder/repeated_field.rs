@@ -0,0 +1,10 @@
+use stated::StatedBuilder;
+
+#[derive(StatedBuilder)]
+pub struct Message {
+    body: String,
+}
+
+fn main() {
+    MessageBuilder::new().body(String::new()).body(String::new());
+}
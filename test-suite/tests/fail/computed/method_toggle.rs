@@ -0,0 +1,24 @@
+use stated::stated;
+
+#[stated(states(Open))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(toggle(Open))]
+    pub fn toggle(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assert(Open))]
+    pub fn is_open(&self) {}
+}
+
+fn main() {
+    Test::new().toggle().toggle().is_open();
+}
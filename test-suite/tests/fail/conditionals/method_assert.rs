@@ -0,0 +1,24 @@
+use stated::stated;
+
+#[stated(states(Buffered, Clean))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(when(Buffered => assign(Clean)))]
+    pub fn flush(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assert(Clean))]
+    pub fn is_clean(&self) {}
+}
+
+fn main() {
+    Test::new().flush().is_clean();
+}
@@ -0,0 +1,28 @@
+use stated::{stated, Y};
+
+#[stated(states(Named))]
+pub struct Config<#[stated] S> {
+    name: String,
+}
+
+#[stated]
+impl<#[stated] S> Config<S> {
+    #[stated]
+    pub const fn new() -> Config<_> {
+        Config {
+            name: String::new(),
+        }
+    }
+
+    #[stated(assign(Named))]
+    pub const fn with_name(mut self, name: String) -> Config<_> {
+        self.name = name;
+        _
+    }
+}
+
+const CONFIG: Config<Y> = Config::new().with_name(String::new());
+
+fn main() {
+    let _ = CONFIG;
+}
@@ -0,0 +1,21 @@
+use stated::stated;
+
+#[stated(counter(Retries, max = 1))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(increment(Retries))]
+    pub fn retry(self) -> Test<_> {
+        _
+    }
+}
+
+fn main() {
+    Test::new().retry().retry();
+}
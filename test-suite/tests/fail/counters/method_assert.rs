@@ -0,0 +1,24 @@
+use stated::stated;
+
+#[stated(counter(Signatures))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(increment(Signatures))]
+    pub fn sign(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assert(Signatures >= 2))]
+    pub fn submit(self) {}
+}
+
+fn main() {
+    Test::new().sign().submit();
+}
@@ -0,0 +1,20 @@
+use stated::stated;
+
+#[stated(states(A), preset(A))]
+pub struct Test<#[stated] S = Initial>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(reject(A))]
+    pub fn foo(self) {}
+}
+
+fn main() {
+    let test: Test = Test::new();
+    test.foo();
+}
@@ -0,0 +1,25 @@
+use stated::stated;
+
+#[stated(states(HasBody))]
+pub struct Test<#[stated] S> {
+    #[stated(when(HasBody) = String)]
+    body: (),
+}
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test { body: () }
+    }
+
+    // The body changes type, so the struct can't be reconstructed as is.
+    #[stated(assign(HasBody))]
+    pub fn body(self) -> Test<_> {
+        _
+    }
+}
+
+fn main() {
+    Test::new().body();
+}
@@ -0,0 +1,21 @@
+use stated::stated;
+
+#[stated(states(A))]
+pub enum Test<#[stated] S> {
+    Unit,
+}
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test::Unit
+    }
+
+    #[stated(assert(A))]
+    pub fn foo(self) {}
+}
+
+fn main() {
+    Test::new().foo();
+}
@@ -0,0 +1,19 @@
+use stated::stated;
+
+#[stated(group(Phase = Idle | Running | Stopped))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(assert(Phase = Running))]
+    pub fn stop(self) {}
+}
+
+fn main() {
+    Test::new().stop();
+}
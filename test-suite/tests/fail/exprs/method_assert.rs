@@ -0,0 +1,33 @@
+use stated::stated;
+
+#[stated(states(HasEmail, HasPhone))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(assign(HasEmail))]
+    pub fn email(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assign(HasPhone))]
+    pub fn phone(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assert(any(HasEmail, HasPhone)))]
+    pub fn verify(self) {}
+
+    #[stated(reject(all(HasEmail, HasPhone)))]
+    pub fn single_contact(self) {}
+}
+
+fn main() {
+    Test::new().verify();
+    Test::new().email().phone().single_contact();
+}
@@ -0,0 +1,24 @@
+use stated::stated;
+
+#[stated(states(conn::Open, auth::LoggedIn))]
+pub struct Test<#[stated(conn)] C, #[stated(auth)] A>;
+
+#[stated]
+impl<#[stated(conn)] C, #[stated(auth)] A> Test<C, A> {
+    #[stated]
+    pub fn new() -> Test<_, _> {
+        Test
+    }
+
+    #[stated(assign(conn::Open))]
+    pub fn open(self) -> Test<_, _> {
+        _
+    }
+
+    #[stated(assert(conn::Open, auth::LoggedIn))]
+    pub fn foo(self) {}
+}
+
+fn main() {
+    Test::new().open().foo();
+}
@@ -0,0 +1,26 @@
+use stated::stated;
+
+#[stated(states(A))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(assign(A))]
+    pub fn a(self) -> Test<_> {
+        _
+    }
+
+    #[stated]
+    pub fn merge(self, _other: Test<S>) -> Test<S> {
+        self
+    }
+}
+
+fn main() {
+    Test::new().a().merge(Test::new());
+}
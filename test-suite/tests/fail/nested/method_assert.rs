@@ -0,0 +1,24 @@
+use stated::stated;
+
+#[stated(states(Connected { Authenticated }))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(assign(Authenticated))]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(delete(Connected))]
+    pub fn close(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assert(Authenticated))]
+    pub fn is_authenticated(&self) {}
+}
+
+fn main() {
+    Test::new().close().is_authenticated();
+}
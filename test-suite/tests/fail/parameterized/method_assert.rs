@@ -0,0 +1,22 @@
+use stated::stated;
+
+pub struct Gzip;
+pub struct Zstd;
+
+#[stated(states(Encoding<E>))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(assign(Encoding<Zstd>))]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(assert(Encoding<Gzip>))]
+    pub fn is_gzip(&self) {}
+}
+
+fn main() {
+    Test::new().is_gzip();
+}
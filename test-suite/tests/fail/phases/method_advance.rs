@@ -0,0 +1,26 @@
+use stated::stated;
+
+#[stated(phases(Created, Connected, Closed))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(advance(Connected))]
+    pub fn connect(self) -> Test<_> {
+        _
+    }
+
+    #[stated(advance(Closed))]
+    pub fn close(self) -> Test<_> {
+        _
+    }
+}
+
+fn main() {
+    let _ = Test::new().close().connect();
+}
@@ -0,0 +1,19 @@
+use stated::stated;
+
+#[stated(phases(Created, Connected, Closed))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(assert(at_least(Connected)))]
+    pub fn is_connected(&self) {}
+}
+
+fn main() {
+    Test::new().is_connected();
+}
@@ -0,0 +1,19 @@
+use stated::stated;
+
+#[stated(states(A))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Box<Test<_>> {
+        Box::new(Test)
+    }
+
+    #[stated(assert(A))]
+    pub fn foo(self: Box<Self>) {}
+}
+
+fn main() {
+    Test::new().foo();
+}
@@ -0,0 +1,24 @@
+use stated::stated;
+
+#[stated(states(A))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Self {
+        Self
+    }
+
+    #[stated(assign(A))]
+    pub fn a(self) -> Self {
+        _
+    }
+
+    #[stated(assert(A))]
+    pub fn foo(self) {}
+}
+
+fn main() {
+    Test::new().a().foo();
+}
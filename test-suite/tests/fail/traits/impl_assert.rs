@@ -0,0 +1,25 @@
+use std::fmt::{self, Display, Formatter};
+
+use stated::stated;
+
+#[stated(states(A))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+}
+
+#[stated(assert(A))]
+impl<#[stated] S> Display for Test<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Hello, World!")
+    }
+}
+
+fn main() {
+    Test::new().to_string();
+}
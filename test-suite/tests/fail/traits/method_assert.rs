@@ -0,0 +1,26 @@
+use stated::stated;
+
+#[stated(states(A))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+}
+
+#[stated]
+impl<#[stated] S> Iterator for Test<S> {
+    type Item = ();
+
+    #[stated(assert(A))]
+    fn next(&mut self) -> Option<Self::Item> {
+        None
+    }
+}
+
+fn main() {
+    Test::new().next();
+}
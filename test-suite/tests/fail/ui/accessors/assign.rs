@@ -0,0 +1,9 @@
+use stated::stated;
+
+#[stated(states(HasRecipient))]
+pub struct Test<#[stated] S> {
+    #[stated(get, assign(HasRecipient))]
+    recipients: Vec<String>,
+}
+
+fn main() {}
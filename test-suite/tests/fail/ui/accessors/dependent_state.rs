@@ -0,0 +1,16 @@
+use stated::stated;
+
+#[stated(states(HasBody))]
+pub struct Test<#[stated] S> {
+    #[stated(when(HasBody) = String)]
+    #[stated(get)]
+    body: (),
+}
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn foo(self) {}
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use stated::stated;
+
+#[stated(states(HasBody))]
+pub struct Test<#[stated] S>(#[stated(get, assert(HasBody))] String);
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn foo(self) {}
+}
+
+fn main() {}
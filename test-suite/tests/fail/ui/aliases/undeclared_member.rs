@@ -0,0 +1,12 @@
+use stated::stated;
+
+#[stated(states(A, B), alias(Both = A + C))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(assert(Both))]
+    pub fn foo(self) {}
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use stated::stated;
+
+#[stated(states(A))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(borrow)]
+    pub fn foo(&mut self) {}
+}
+
+fn main() {}
@@ -1,11 +1,12 @@
-use stated::stated;
-
-pub trait MyTrait {}
-
-#[stated(states(A))]
-pub struct Test<#[stated] S>;
-
-#[stated]
-impl<#[stated] S> MyTrait for Test<S> {}
-
-fn main() {}
+use stated::stated;
+
+#[stated(states(A))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(borrow)]
+    pub fn foo() {}
+}
+
+fn main() {}
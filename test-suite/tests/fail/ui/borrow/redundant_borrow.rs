@@ -0,0 +1,12 @@
+use stated::stated;
+
+#[stated(states(A))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(borrow, borrow)]
+    pub fn foo(&self) {}
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use stated::stated;
+
+#[stated(states(A))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn foo(&mut self) -> Test<_> {
+        _
+    }
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use stated::stated;
+
+pub trait Peek {
+    fn peek(&mut self) -> u8;
+}
+
+#[stated(states(A))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Peek for Test<S> {
+    #[stated(assign(A))]
+    fn peek(&mut self) -> u8 {
+        0
+    }
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use stated::StatedBuilder;
+
+#[derive(StatedBuilder)]
+pub struct Message {
+    #[builder(skip)]
+    body: String,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use stated::StatedBuilder;
+
+#[derive(StatedBuilder)]
+pub struct Message(String);
+
+fn main() {}
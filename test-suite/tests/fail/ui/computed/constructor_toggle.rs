@@ -0,0 +1,14 @@
+use stated::stated;
+
+#[stated(states(A))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(toggle(A))]
+    pub fn new() -> Test<_> {
+        Test
+    }
+}
+
+fn main() {}
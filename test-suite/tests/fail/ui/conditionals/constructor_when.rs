@@ -0,0 +1,14 @@
+use stated::stated;
+
+#[stated(states(A, B))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(when(A => assign(B)))]
+    pub fn new() -> Test<_> {
+        Test
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use stated::stated;
+
+#[stated(states(A, B))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(when(A => assign(C)))]
+    pub fn foo(self) {}
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use stated::stated;
+
+#[stated(states(A))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(assign(A))]
+    pub const fn a(self: Box<Self>) -> Box<Test<_>> {
+        _
+    }
+}
+
+fn main() {}
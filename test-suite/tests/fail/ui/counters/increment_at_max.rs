@@ -0,0 +1,14 @@
+use stated::stated;
+
+#[stated(counter(Retries, max = 2))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(assert(Retries == 2), increment(Retries))]
+    pub fn foo(self) -> Test<_> {
+        _
+    }
+}
+
+fn main() {}
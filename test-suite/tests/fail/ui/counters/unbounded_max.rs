@@ -0,0 +1,12 @@
+use stated::stated;
+
+#[stated(counter(Retries))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(assert(Retries <= 2))]
+    pub fn foo(self) {}
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use stated::stated;
+
+#[stated(states(HasBody))]
+pub struct Test<#[stated] S> {
+    #[stated(otherwise = ())]
+    body: String,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use stated::stated;
+
+#[stated(states(Encoding<E>))]
+pub struct Test<#[stated] S> {
+    #[stated(when(Encoding) = String)]
+    encoded: (),
+}
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn foo(self) {}
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use stated::stated;
+
+#[stated(states(HasBody))]
+pub struct Test<#[stated] S> {
+    #[stated(when(HasSubject) = String)]
+    subject: (),
+}
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn foo(self) {}
+}
+
+fn main() {}
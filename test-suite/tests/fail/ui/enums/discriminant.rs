@@ -0,0 +1,15 @@
+use stated::stated;
+
+#[stated(states(A))]
+pub enum Test<#[stated] S> {
+    Ok = 0,
+    Err = 1,
+}
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn foo(self) {}
+}
+
+fn main() {}
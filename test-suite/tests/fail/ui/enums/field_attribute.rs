@@ -0,0 +1,17 @@
+use stated::stated;
+
+#[stated(states(A))]
+pub enum Test<#[stated] S> {
+    V {
+        #[stated(get)]
+        x: u8,
+    },
+}
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn foo(self) {}
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use stated::stated;
+
+#[stated(group(Phase = Idle | Running))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(assert(Phase = Idle, Phase = Running))]
+    pub fn foo(self) {}
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use stated::stated;
+
+#[stated(group(Phase = Idle | Running | Idle))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {}
+
+fn main() {}
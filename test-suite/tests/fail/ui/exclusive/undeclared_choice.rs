@@ -0,0 +1,14 @@
+use stated::stated;
+
+#[stated(group(Phase = Idle | Running))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(assign(Phase = Stopped))]
+    pub fn foo(self) -> Test<_> {
+        _
+    }
+}
+
+fn main() {}
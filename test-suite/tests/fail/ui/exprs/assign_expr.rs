@@ -0,0 +1,14 @@
+use stated::stated;
+
+#[stated(states(A, B))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(assign(any(A, B)))]
+    pub fn foo(self) -> Test<_> {
+        _
+    }
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use stated::stated;
+
+#[stated(states(conn::Open, file::Open))]
+pub struct Test<#[stated(conn)] C, #[stated(file)] F>;
+
+#[stated]
+impl<#[stated(conn)] C, #[stated(file)] F> Test<C, F> {}
+
+fn main() {}
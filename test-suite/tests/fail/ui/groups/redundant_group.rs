@@ -0,0 +1,9 @@
+use stated::stated;
+
+#[stated(states(conn::Open))]
+pub struct Test<#[stated(conn)] C, #[stated(conn)] D>;
+
+#[stated]
+impl<#[stated(conn)] C, #[stated(conn)] D> Test<C, D> {}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use stated::stated;
+
+#[stated(states(conn::Open))]
+pub struct Test<#[stated(conn)] C, #[stated(auth)] A>;
+
+#[stated]
+impl<#[stated(conn)] C, #[stated(auth)] A> Test<C, A> {}
+
+fn main() {}
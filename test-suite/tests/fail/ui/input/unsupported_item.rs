@@ -1,9 +1,11 @@
-use stated::stated;
-
-#[stated(states(A))]
-pub enum Test<#[stated] S> {}
-
-#[stated]
-impl<#[stated] S> Test<S> {}
-
-fn main() {}
+use stated::stated;
+
+#[stated(states(A))]
+pub union Test<#[stated] S> {
+    x: u8,
+}
+
+#[stated]
+impl<#[stated] S> Test<S> {}
+
+fn main() {}
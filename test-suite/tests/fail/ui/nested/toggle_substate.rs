@@ -0,0 +1,12 @@
+use stated::stated;
+
+#[stated(states(Connected { Authenticated }))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(toggle(Authenticated))]
+    pub fn foo(self) {}
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use stated::stated;
+
+#[stated(states(Encoding<E>))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(assign(Encoding))]
+    pub fn foo(self) {}
+}
+
+fn main() {}
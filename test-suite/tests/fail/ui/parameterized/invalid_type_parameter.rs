@@ -0,0 +1,12 @@
+use stated::stated;
+
+#[stated(states(Encoding<Vec<u8>>))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(delete(Encoding))]
+    pub fn foo(self) {}
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use stated::stated;
+
+pub struct Gzip;
+
+#[stated(states(Encoding))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(assign(Encoding<Gzip>))]
+    pub fn foo(self) {}
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use stated::stated;
+
+#[stated(phases(Created, Connected), states(Connected))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(advance(Connected))]
+    pub fn foo(self) {}
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use stated::stated;
+
+#[stated(phases(Created, Connected, Closed))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(reject(Connected))]
+    pub fn foo(self) {}
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use stated::stated;
+
+#[stated(phases(Created, Connected), states(Closed))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(advance(Closed))]
+    pub fn foo(self) {}
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use stated::stated;
+
+#[stated(states(A))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(assign(A))]
+    pub fn foo(self: std::pin::Pin<Box<Self>>) -> Test<_> {
+        _
+    }
+}
+
+fn main() {}
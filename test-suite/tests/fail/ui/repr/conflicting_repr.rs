@@ -0,0 +1,15 @@
+use stated::stated;
+
+#[stated(states(A), repr_transparent_states)]
+#[repr(C)]
+pub struct Test<#[stated] S> {
+    x: i32,
+}
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn foo(self) {}
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use stated::stated;
+
+#[stated(states(A), repr_transparent_states)]
+pub enum Test<#[stated] S> {
+    Unit,
+}
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn foo(self) {}
+}
+
+fn main() {}
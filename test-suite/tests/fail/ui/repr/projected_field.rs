@@ -0,0 +1,28 @@
+use stated::{stated, N, Y};
+
+pub trait Pick {
+    type T;
+}
+
+impl Pick for N {
+    type T = usize;
+}
+
+impl Pick for Y {
+    type T = &'static u64;
+}
+
+#[stated(states(A), repr_transparent_states)]
+pub struct Test<#[stated] S: Pick> {
+    x: <S as Pick>::T,
+}
+
+#[stated]
+impl<#[stated] S: Pick> Test<S> {
+    #[stated(assign(A))]
+    pub fn a(self) -> Test<_> {
+        _
+    }
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use stated::stated;
+
+#[stated(states(A))]
+pub struct Test<#[stated] S>;
+
+#[stated(assign(A))]
+impl<#[stated] S> Test<S> {}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use stated::stated;
+
+pub trait MyTrait {
+    fn foo(&self);
+
+    fn bar(&self);
+}
+
+#[stated(states(A))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> MyTrait for Test<S> {
+    #[stated(assert(A))]
+    fn foo(&self) {}
+
+    #[stated(reject(A))]
+    fn bar(&self) {}
+}
+
+fn main() {}
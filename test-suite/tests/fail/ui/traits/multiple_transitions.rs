@@ -0,0 +1,29 @@
+use stated::stated;
+
+pub trait Transition {
+    type Output;
+
+    fn a(self) -> Self::Output;
+
+    fn b(self) -> Self::Output;
+}
+
+#[stated(states(A, B))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Transition for Test<S> {
+    type Output = Test<_>;
+
+    #[stated(assign(A))]
+    fn a(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assign(B))]
+    fn b(self) -> Test<_> {
+        _
+    }
+}
+
+fn main() {}
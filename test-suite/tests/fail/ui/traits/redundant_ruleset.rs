@@ -0,0 +1,17 @@
+use stated::stated;
+
+pub trait MyTrait {
+    fn foo(&self);
+}
+
+#[stated(states(A, B))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> MyTrait for Test<S> {
+    #[stated(assert(A))]
+    #[stated(assert(B))]
+    fn foo(&self) {}
+}
+
+fn main() {}
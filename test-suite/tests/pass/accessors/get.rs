@@ -0,0 +1,50 @@
+use stated::stated;
+
+#[stated(states(HasRecipient, HasBody))]
+pub struct MessageBuilder<#[stated] S> {
+    #[stated(get, assert(HasRecipient))]
+    #[stated(get_mut, assert(HasRecipient), reject(HasBody))]
+    recipients: Vec<String>,
+    #[stated(when(HasBody) = String)]
+    #[stated(get, assert(HasBody))]
+    body: (),
+    #[stated(get)]
+    subject: Option<String>,
+}
+
+#[stated]
+impl<#[stated] S> MessageBuilder<S> {
+    #[stated]
+    pub fn new() -> MessageBuilder<_> {
+        MessageBuilder {
+            recipients: Vec::new(),
+            body: (),
+            subject: None,
+        }
+    }
+
+    #[stated(assign(HasRecipient))]
+    pub fn recipient(mut self, recipient: &str) -> MessageBuilder<_> {
+        self.recipients.push(recipient.to_owned());
+        _
+    }
+
+    #[stated(reject(HasBody), assign(HasBody))]
+    pub fn body(self, body: &str) -> MessageBuilder<_> {
+        MessageBuilder {
+            recipients: self.recipients,
+            body: body.to_owned(),
+            subject: self.subject,
+        }
+    }
+}
+
+fn main() {
+    let mut builder = MessageBuilder::new().recipient("alice");
+    builder.recipients_mut().push("bob".to_owned());
+    assert_eq!(builder.recipients(), &["alice", "bob"]);
+    assert!(builder.subject().is_none());
+
+    let builder = builder.body("Hello");
+    assert_eq!(builder.body(), "Hello");
+}
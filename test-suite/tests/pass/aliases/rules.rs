@@ -0,0 +1,46 @@
+use stated::stated;
+
+#[stated(
+    states(HasRecipient, HasSubject, HasBody),
+    alias(Complete = HasRecipient + HasSubject + HasBody)
+)]
+pub struct Message<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Message<S> {
+    #[stated]
+    pub fn new() -> Message<_> {
+        Message
+    }
+
+    #[stated(assign(HasRecipient))]
+    pub fn recipient(self) -> Message<_> {
+        _
+    }
+
+    #[stated(assign(HasSubject))]
+    pub fn subject(self) -> Message<_> {
+        _
+    }
+
+    #[stated(assign(HasBody))]
+    pub fn body(self) -> Message<_> {
+        _
+    }
+
+    #[stated(assert(Complete), delete(Complete))]
+    pub fn send(self) -> Message<_> {
+        _
+    }
+
+    #[stated(reject(Complete))]
+    pub fn is_draft(&self) -> bool {
+        true
+    }
+}
+
+fn main() {
+    let message = Message::new().recipient().subject();
+    assert!(message.is_draft());
+    assert!(message.body().send().is_draft());
+}
@@ -0,0 +1,40 @@
+use stated::stated;
+
+#[stated(
+    states(HasRecipient, HasSubject, HasBody),
+    alias(Complete = HasRecipient + HasSubject + HasBody),
+    alias(Addressed = HasRecipient)
+)]
+pub struct Message<'a, #[stated] S> {
+    recipient: &'a str,
+}
+
+#[stated]
+impl<'a, #[stated] S> Message<'a, S> {
+    #[stated]
+    pub fn new(recipient: &'a str) -> Message<'a, _> {
+        Message { recipient }
+    }
+
+    #[stated(assign(HasSubject, HasBody))]
+    pub fn compose(self) -> Message<'a, _> {
+        _
+    }
+
+    #[stated(assign(Addressed))]
+    pub fn address(self) -> Message<'a, _> {
+        _
+    }
+}
+
+fn send(message: MessageComplete<'_>) -> &str {
+    message.recipient
+}
+
+fn addressed<HasSubject, HasBody>(_: &MessageAddressed<'_, HasSubject, HasBody>) {}
+
+fn main() {
+    let message = Message::new("them").compose().address();
+    addressed(&message);
+    assert_eq!(send(message), "them");
+}
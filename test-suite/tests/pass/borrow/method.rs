@@ -0,0 +1,68 @@
+use stated::{stated, N, Y};
+
+pub trait Peek {
+    fn value(&self) -> i32;
+}
+
+#[stated(states(A, B), counter(Retries))]
+pub struct Test<#[stated] S> {
+    x: i32,
+}
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new(x: i32) -> Test<_> {
+        Test { x }
+    }
+
+    #[stated(assign(A))]
+    pub fn a(self) -> Test<_> {
+        _
+    }
+
+    #[stated(increment(Retries))]
+    pub fn retry(self) -> Test<_> {
+        _
+    }
+
+    #[stated(borrow)]
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+
+    #[stated(borrow, assert(A))]
+    pub fn peek(&self) -> &Test<_> {
+        self
+    }
+
+    #[stated(reject(B))]
+    pub fn set_x(&mut self, x: i32) {
+        self.x = x;
+    }
+
+    #[stated(assert(A))]
+    pub fn typed(self: &Self) -> i32 {
+        self.x
+    }
+}
+
+#[stated]
+impl<#[stated] S> Peek for Test<S> {
+    #[stated(borrow, assert(A))]
+    fn value(&self) -> i32 {
+        self.x
+    }
+}
+
+fn main() {
+    let mut test = Test::new(1);
+    assert_eq!(test.x(), 1);
+    test.set_x(2);
+
+    let test = test.a().retry().retry();
+    let _: &Test<(Y, N, stated::Succ<stated::Succ<stated::Zero>>)> = test.peek();
+    assert_eq!(test.x(), 2);
+    assert_eq!(test.typed(), 2);
+    assert_eq!(test.value(), 2);
+}
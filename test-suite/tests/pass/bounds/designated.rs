@@ -0,0 +1,68 @@
+use std::fmt::Debug;
+
+use stated::stated;
+
+#[stated(states(A, B))]
+#[derive(Debug)]
+pub struct Test<#[stated] S: Send + 'static>
+where
+    S: Debug,
+{
+    value: i32,
+}
+
+#[stated]
+impl<#[stated] S: Send + 'static> Test<S>
+where
+    S: Debug,
+{
+    #[stated]
+    pub fn new(value: i32) -> Test<_> {
+        Test { value }
+    }
+
+    #[stated(assign(A))]
+    pub fn a(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assert(A), assign(B))]
+    pub fn b(self) -> Test<_> {
+        _
+    }
+
+    #[stated]
+    pub fn describe(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+fn spawn<T: Send + 'static>(value: T) -> T {
+    std::thread::spawn(move || value).join().unwrap()
+}
+
+#[stated(states(Open))]
+pub enum Connection<#[stated] S: Clone> {
+    Tcp(u16),
+}
+
+#[stated]
+impl<#[stated] S: Clone> Connection<S> {
+    #[stated]
+    pub fn new(port: u16) -> Connection<_> {
+        Connection::Tcp(port)
+    }
+
+    #[stated(assign(Open))]
+    pub fn open(self) -> Connection<_> {
+        _
+    }
+}
+
+fn main() {
+    let test = spawn(Test::new(1).a());
+    assert!(test.b().describe().contains("value: 1"));
+
+    let Connection::Tcp(port, ..) = Connection::new(80).open();
+    assert_eq!(port, 80);
+}
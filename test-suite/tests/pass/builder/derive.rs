@@ -0,0 +1,41 @@
+use stated::StatedBuilder;
+
+#[derive(StatedBuilder)]
+pub struct Message {
+    recipients: Vec<String>,
+    body: String,
+    #[builder(default)]
+    subject: Option<String>,
+    #[builder(default = 3)]
+    retries: u8,
+}
+
+#[derive(StatedBuilder)]
+pub struct Wrapper<'a, T> {
+    inner: &'a T,
+}
+
+fn main() {
+    let message = MessageBuilder::new()
+        .body("Hello".to_owned())
+        .subject(Some("Greeting".to_owned()))
+        .recipients(vec!["alice".to_owned()])
+        .build();
+
+    assert_eq!(message.recipients, ["alice"]);
+    assert_eq!(message.body, "Hello");
+    assert_eq!(message.subject.as_deref(), Some("Greeting"));
+    assert_eq!(message.retries, 3);
+
+    let message = Message::builder()
+        .recipients(Vec::new())
+        .retries(5)
+        .body(String::new())
+        .build();
+
+    assert!(message.subject.is_none());
+    assert_eq!(message.retries, 5);
+
+    let value = 1;
+    assert_eq!(*Wrapper::builder().inner(&value).build().inner, 1);
+}
@@ -0,0 +1,30 @@
+use stated::StatedBuilder;
+
+#[derive(StatedBuilder)]
+pub struct Options {
+    #[builder(default)]
+    verbose: bool,
+    #[builder(default = 8080)]
+    port: u16,
+}
+
+#[derive(StatedBuilder)]
+pub struct Wrapper<T: Default> {
+    #[builder(default)]
+    inner: T,
+}
+
+fn main() {
+    let options = OptionsBuilder::new().build();
+    assert!(!options.verbose);
+    assert_eq!(options.port, 8080);
+
+    let options = Options::builder().port(80).verbose(true).port(443).build();
+    assert!(options.verbose);
+    assert_eq!(options.port, 443);
+
+    let _: OptionsBuilder = Options::builder();
+
+    assert_eq!(Wrapper::<u8>::builder().build().inner, 0);
+    assert_eq!(Wrapper::builder().inner(1).build().inner, 1);
+}
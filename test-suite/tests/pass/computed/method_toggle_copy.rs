@@ -0,0 +1,42 @@
+use stated::stated;
+
+#[stated(states(Modified, Dirty, Open))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(assign(Modified))]
+    pub fn modify(self) -> Test<_> {
+        _
+    }
+
+    #[stated(toggle(Open))]
+    pub fn toggle(self) -> Test<_> {
+        _
+    }
+
+    #[stated(copy(Dirty = Modified))]
+    pub fn sync(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assert(Open, Dirty))]
+    pub fn is_open_and_dirty(&self) -> bool {
+        true
+    }
+
+    #[stated(reject(Open, Dirty))]
+    pub fn is_closed_and_clean(&self) -> bool {
+        true
+    }
+}
+
+fn main() {
+    assert!(Test::new().modify().toggle().sync().is_open_and_dirty());
+    assert!(Test::new().sync().toggle().toggle().is_closed_and_clean());
+}
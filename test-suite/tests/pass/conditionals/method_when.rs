@@ -0,0 +1,37 @@
+use stated::stated;
+
+#[stated(states(Buffered, Clean, Dirty))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(assign(Dirty))]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(assign(Buffered))]
+    pub fn buffer(self) -> Test<_> {
+        _
+    }
+
+    #[stated(delete(Buffered), when(Buffered => assign(Clean), delete(Dirty)))]
+    pub fn flush(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assert(Clean), reject(Dirty))]
+    pub fn is_clean(&self) -> bool {
+        true
+    }
+
+    #[stated(reject(Clean), assert(Dirty))]
+    pub fn is_dirty(&self) -> bool {
+        true
+    }
+}
+
+fn main() {
+    assert!(Test::new().flush().is_dirty());
+    assert!(Test::new().buffer().flush().is_clean());
+}
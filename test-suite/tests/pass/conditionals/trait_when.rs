@@ -0,0 +1,43 @@
+use stated::stated;
+
+#[stated(states(A, B, C))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated(assign(B))]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(assign(A))]
+    pub fn a(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assert(C))]
+    pub fn c(&self) -> bool {
+        true
+    }
+}
+
+pub trait Step {
+    type Next;
+
+    fn step(self) -> Self::Next;
+}
+
+#[stated]
+impl<#[stated] S> Step for Test<S> {
+    type Next = Test<_>;
+
+    #[stated(when(any(A, not(B)), B => assign(C)))]
+    fn step(self) -> Self::Next {
+        _
+    }
+}
+
+fn main() {
+    assert!(Test::new().a().step().c());
+    let _ = Test::new().step();
+}
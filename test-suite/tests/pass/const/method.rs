@@ -0,0 +1,95 @@
+use stated::{stated, Choice, N, Y};
+
+#[stated(states(Port, Host), group(Mode = Fast | Slow))]
+pub struct Config<#[stated] S> {
+    port: u16,
+    host: &'static str,
+}
+
+#[stated]
+impl<#[stated] S> Config<S> {
+    #[stated]
+    pub const fn new() -> Config<_> {
+        Config { port: 0, host: "" }
+    }
+
+    #[stated(assign(Port))]
+    pub const fn with_port(mut self, port: u16) -> Config<_> {
+        self.port = port;
+        _
+    }
+
+    #[stated(assign(Host, Mode = Slow))]
+    pub const fn with_host(mut self, host: &'static str) -> Config<_> {
+        self.host = host;
+        _
+    }
+
+    #[stated(borrow, assert(Port))]
+    pub const fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+#[stated(states(Open), repr_transparent_states)]
+pub struct Buffer<#[stated] S> {
+    data: [u8; 64],
+    name: String,
+}
+
+impl<S> Drop for Buffer<S> {
+    fn drop(&mut self) {}
+}
+
+#[stated]
+impl<#[stated] S> Buffer<S> {
+    #[stated]
+    pub const fn new() -> Buffer<_> {
+        Buffer {
+            data: [0; 64],
+            name: String::new(),
+        }
+    }
+
+    #[stated(assign(Open))]
+    pub const fn open(self) -> Buffer<_> {
+        _
+    }
+}
+
+#[stated(states(Open))]
+pub enum Connection<#[stated] S> {
+    Tcp(u16),
+    Unix { path: String },
+}
+
+#[stated]
+impl<#[stated] S> Connection<S> {
+    #[stated]
+    pub const fn tcp(port: u16) -> Connection<_> {
+        Connection::Tcp(port)
+    }
+
+    #[stated(assign(Open))]
+    pub const fn open(self) -> Connection<_> {
+        _
+    }
+}
+
+const CONFIG: Config<(Y, N, Choice<0>)> = Config::new().with_port(80);
+const PORT: u16 = CONFIG.port();
+const HOST: Config<(Y, Y, Choice<1>)> = CONFIG.with_host("localhost");
+const BUFFER: Buffer<Y> = Buffer::new().open();
+const CONNECTION: Connection<Y> = Connection::tcp(80).open();
+
+fn main() {
+    assert_eq!(PORT, 80);
+    assert_eq!(HOST.port(), 80);
+    assert_eq!(HOST.host, "localhost");
+    assert_eq!(BUFFER.data, [0; 64]);
+    assert!(BUFFER.name.is_empty());
+    assert!(matches!(CONNECTION, Connection::Tcp(80, ..)));
+
+    let buffer: Buffer<Y> = Buffer::new().open();
+    assert!(buffer.name.is_empty());
+}
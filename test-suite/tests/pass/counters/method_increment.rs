@@ -0,0 +1,43 @@
+use stated::{stated, Succ, Zero, N};
+
+#[stated(states(Sent), counter(Retries, max = 2), counter(Signatures))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(increment(Retries))]
+    pub fn retry(self) -> Test<_> {
+        _
+    }
+
+    #[stated(increment(Signatures))]
+    pub fn sign(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assert(Signatures >= 2, Retries <= 1))]
+    pub fn submit(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assert(Retries == 0))]
+    pub fn first_try(&self) -> bool {
+        true
+    }
+}
+
+type Two<T> = Succ<Succ<T>>;
+
+fn main() {
+    let _: Test<(N, (Zero, Two<Zero>), Zero)> = Test::new();
+    let _: Test<(N, (Succ<Zero>, Succ<Zero>), Zero)> = Test::new().retry();
+    let _: Test<(N, (Two<Zero>, Zero), Zero)> = Test::new().retry().retry();
+    let _: Test<(N, (Succ<Zero>, Succ<Zero>), Two<Zero>)> =
+        Test::new().sign().retry().sign().submit();
+    assert!(Test::new().sign().first_try());
+}
@@ -0,0 +1,71 @@
+use stated::{stated, StatedBuilder, N, Y};
+
+#[stated(states(A, B, Body<T>), preset(B, Body<String>), counter(Retries))]
+pub struct Test<#[stated] S = Initial> {
+    x: i32,
+}
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new(x: i32) -> Test<_> {
+        Test { x }
+    }
+
+    #[stated(assign(A))]
+    pub fn a(self) -> Test<_> {
+        _
+    }
+}
+
+#[stated(states(conn::Open, auth::LoggedIn), preset(auth::LoggedIn))]
+pub struct Grouped<T, #[stated(conn)] C = Initial, #[stated(auth)] A = Initial> {
+    x: T,
+}
+
+#[stated]
+impl<T, #[stated(conn)] C, #[stated(auth)] A> Grouped<T, C, A> {
+    #[stated]
+    pub fn new(x: T) -> Grouped<T, _, _> {
+        Grouped { x }
+    }
+}
+
+#[stated(states(Open), preset(Open))]
+pub enum Connection<#[stated] S = Initial> {
+    Tcp(u16),
+}
+
+#[stated]
+impl<#[stated] S> Connection<S> {
+    #[stated]
+    pub fn new(port: u16) -> Connection<_> {
+        Connection::Tcp(port)
+    }
+}
+
+#[derive(StatedBuilder)]
+pub struct Message {
+    body: String,
+}
+
+pub struct Holder {
+    test: Test,
+    grouped: Grouped<i32>,
+    connection: Connection,
+    builder: MessageBuilder,
+}
+
+fn main() {
+    let holder = Holder {
+        test: Test::new(1),
+        grouped: Grouped::new(2),
+        connection: Connection::new(80),
+        builder: MessageBuilder::new(),
+    };
+
+    let _: Test<(Y, Y, stated::With<String>, stated::Zero)> = holder.test.a();
+    let _: Grouped<i32, N, Y> = holder.grouped;
+    let _: Connection<Y> = holder.connection;
+    assert_eq!(holder.builder.body("Hello".to_owned()).build().body, "Hello");
+}
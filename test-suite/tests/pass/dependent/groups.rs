@@ -0,0 +1,51 @@
+use stated::stated;
+
+#[stated(states(conn::Open, auth::LoggedIn, auth::Admin))]
+pub struct Session<#[stated(conn)] C, #[stated(auth)] A> {
+    #[stated(when(conn::Open) = u16)]
+    port: (),
+    #[stated(when(auth::LoggedIn) = String, otherwise = Option<String>)]
+    user: _,
+}
+
+#[stated]
+impl<#[stated(conn)] C, #[stated(auth)] A> Session<C, A> {
+    #[stated]
+    pub fn new() -> Session<_, _> {
+        Session {
+            port: (),
+            user: None,
+        }
+    }
+
+    #[stated(assign(conn::Open))]
+    pub fn open(self, port: u16) -> Session<_, _> {
+        Session {
+            port,
+            user: self.user,
+        }
+    }
+
+    #[stated(assign(auth::Admin))]
+    pub fn promote(self) -> Session<_, _> {
+        _
+    }
+
+    #[stated(assert(conn::Open), reject(auth::LoggedIn), assign(auth::LoggedIn))]
+    pub fn log_in(self, user: &str) -> Session<_, _> {
+        Session {
+            port: self.port,
+            user: user.to_owned(),
+        }
+    }
+
+    #[stated(assert(conn::Open, auth::LoggedIn))]
+    pub fn describe(&self) -> String {
+        format!("{}@{}", self.user, self.port)
+    }
+}
+
+fn main() {
+    let session = Session::new().open(8080).promote().log_in("alice");
+    assert_eq!(session.describe(), "alice@8080");
+}
@@ -0,0 +1,62 @@
+use stated::stated;
+
+#[stated(states(HasRecipients, HasBody))]
+pub struct MessageBuilder<#[stated] S> {
+    #[stated(when(HasRecipients) = Vec<String>)]
+    recipients: (),
+    #[stated(when(HasBody) = String, otherwise = ())]
+    body: (),
+    subject: Option<String>,
+}
+
+#[stated]
+impl<#[stated] S> MessageBuilder<S> {
+    #[stated]
+    pub fn new() -> MessageBuilder<_> {
+        MessageBuilder {
+            recipients: (),
+            body: (),
+            subject: None,
+        }
+    }
+
+    #[stated(reject(HasRecipients), assign(HasRecipients))]
+    pub fn recipients(self, recipients: Vec<String>) -> MessageBuilder<_> {
+        MessageBuilder {
+            recipients,
+            body: self.body,
+            subject: self.subject,
+        }
+    }
+
+    #[stated(reject(HasBody), assign(HasBody))]
+    pub fn body(self, body: &str) -> MessageBuilder<_> {
+        MessageBuilder {
+            recipients: self.recipients,
+            body: body.to_owned(),
+            subject: self.subject,
+        }
+    }
+
+    #[stated]
+    pub fn subject(mut self, subject: &str) -> MessageBuilder<_> {
+        self.subject = Some(subject.to_owned());
+        _
+    }
+
+    #[stated(assert(HasRecipients, HasBody))]
+    pub fn build(self) -> String {
+        let subject = self.subject.unwrap_or_default();
+        format!("{}: {subject}\n{}", self.recipients.join(", "), self.body)
+    }
+}
+
+fn main() {
+    let message = MessageBuilder::new()
+        .body("Hello")
+        .subject("Greeting")
+        .recipients(vec!["alice".to_owned(), "bob".to_owned()])
+        .build();
+
+    assert_eq!(message, "alice, bob: Greeting\nHello");
+}
@@ -0,0 +1,60 @@
+use std::{cell::Cell, rc::Rc};
+
+use stated::{stated, Y};
+
+#[stated(states(Open))]
+pub enum Handle<#[stated] S> {
+    File(String, Rc<Cell<usize>>),
+    Socket { port: u16, drops: Rc<Cell<usize>> },
+}
+
+impl<S> Drop for Handle<S> {
+    fn drop(&mut self) {
+        let (Handle::File(_, drops, ..) | Handle::Socket { drops, .. }) = self;
+        drops.set(drops.get() + 1);
+    }
+}
+
+#[stated]
+impl<#[stated] S> Handle<S> {
+    #[stated]
+    pub fn file(path: &str, drops: &Rc<Cell<usize>>) -> Handle<_> {
+        Handle::File(path.to_owned(), Rc::clone(drops))
+    }
+
+    #[stated]
+    pub fn socket(port: u16, drops: &Rc<Cell<usize>>) -> Handle<_> {
+        Handle::Socket {
+            port,
+            drops: Rc::clone(drops),
+        }
+    }
+
+    #[stated(assign(Open))]
+    pub fn open(self) -> Handle<_> {
+        _
+    }
+}
+
+fn main() {
+    let drops = Rc::new(Cell::new(0));
+
+    let handle: Handle<Y> = Handle::file("data.txt", &drops).open();
+    assert_eq!(drops.get(), 0);
+    let Handle::File(path, ..) = &handle else {
+        unreachable!();
+    };
+    assert_eq!(path, "data.txt");
+    drop(handle);
+    assert_eq!(drops.get(), 1);
+
+    let handle: Handle<Y> = Handle::socket(80, &drops).open();
+    assert_eq!(drops.get(), 1);
+    let Handle::Socket { port, .. } = &handle else {
+        unreachable!();
+    };
+    assert_eq!(*port, 80);
+    drop(handle);
+    assert_eq!(drops.get(), 2);
+    assert_eq!(Rc::strong_count(&drops), 1);
+}
@@ -0,0 +1,96 @@
+use std::{cell::Cell, rc::Rc};
+
+use stated::{stated, N, Y};
+
+#[stated(states(Locked, Poisoned))]
+pub struct Guard<#[stated] S> {
+    name: String,
+    drops: Rc<Cell<usize>>,
+}
+
+impl<S> Drop for Guard<S> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+#[stated]
+impl<#[stated] S> Guard<S> {
+    #[stated]
+    pub fn new(name: &str, drops: &Rc<Cell<usize>>) -> Guard<_> {
+        Guard {
+            name: name.to_owned(),
+            drops: Rc::clone(drops),
+        }
+    }
+
+    #[stated(assign(Locked))]
+    pub fn lock(self) -> Guard<_> {
+        _
+    }
+
+    #[stated(assert(Locked), delete(Locked))]
+    pub fn unlock(self) -> Guard<_> {
+        _
+    }
+
+    #[stated(assign(Poisoned))]
+    pub fn poison(self: Box<Self>) -> Box<Guard<_>> {
+        _
+    }
+
+    #[stated(borrow)]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[stated(states(Locked), repr_transparent_states)]
+pub struct TransparentGuard<#[stated] S> {
+    drops: Rc<Cell<usize>>,
+}
+
+impl<S> Drop for TransparentGuard<S> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+#[stated]
+impl<#[stated] S> TransparentGuard<S> {
+    #[stated]
+    pub fn new(drops: &Rc<Cell<usize>>) -> TransparentGuard<_> {
+        TransparentGuard {
+            drops: Rc::clone(drops),
+        }
+    }
+
+    #[stated(assign(Locked))]
+    pub fn lock(self) -> TransparentGuard<_> {
+        _
+    }
+}
+
+fn main() {
+    let drops = Rc::new(Cell::new(0));
+
+    let guard = Guard::new("file", &drops);
+    let guard: Guard<(Y, N)> = guard.lock();
+    let guard: Guard<(N, N)> = guard.unlock();
+    assert_eq!(drops.get(), 0);
+    assert_eq!(guard.name(), "file");
+    drop(guard);
+    assert_eq!(drops.get(), 1);
+
+    let guard: Box<Guard<(N, Y)>> = Box::new(Guard::new("lock", &drops)).poison();
+    assert_eq!(drops.get(), 1);
+    assert_eq!(guard.name(), "lock");
+    drop(guard);
+    assert_eq!(drops.get(), 2);
+
+    let guard: TransparentGuard<Y> = TransparentGuard::new(&drops).lock();
+    assert_eq!(drops.get(), 2);
+    drop(guard);
+    assert_eq!(drops.get(), 3);
+    assert_eq!(Rc::strong_count(&drops), 1);
+}
@@ -0,0 +1,32 @@
+use stated::{stated, N, Y};
+
+#[stated(states(A, B), preset(B))]
+pub enum Test<#[stated] S> {
+    Unit,
+    Unnamed(i32),
+    Named { x: i32 },
+}
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn unit() -> Test<_> {
+        Test::Unit
+    }
+
+    #[stated]
+    pub fn unnamed(x: i32) -> Test<_> {
+        Test::Unnamed(x)
+    }
+
+    #[stated]
+    pub fn named(x: i32) -> Test<_> {
+        Test::Named { x }
+    }
+}
+
+fn main() {
+    assert!(matches!(Test::unit(), Test::<(N, Y)>::Unit(..)));
+    assert!(matches!(Test::unnamed(1), Test::<(N, Y)>::Unnamed(1, ..)));
+    assert!(matches!(Test::named(2), Test::<(N, Y)>::Named { x: 2, .. }));
+}
@@ -0,0 +1,44 @@
+use stated::{stated, N, Y};
+
+#[stated(states(A, B))]
+pub enum Test<#[stated] S> {
+    Unit,
+    Unnamed(i32),
+    Named { x: i32 },
+}
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new(x: i32) -> Test<_> {
+        Test::Unnamed(x)
+    }
+
+    #[stated(assign(A))]
+    pub fn foo(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assert(A), assign(B))]
+    pub fn bar(self) -> Test<_> {
+        match self {
+            Test::Unit => Test::Unit,
+            Test::Unnamed(x) => Test::Named { x },
+            Test::Named { x } => Test::Unnamed(x),
+        }
+    }
+
+    #[stated(assert(A, B))]
+    pub fn get(&self) -> Option<i32> {
+        match self {
+            Test::Unit => None,
+            Test::Unnamed(x) | Test::Named { x } => Some(*x),
+        }
+    }
+}
+
+fn main() {
+    assert!(matches!(Test::new(1).foo(), Test::<(Y, N)>::Unnamed(1, ..)));
+    assert!(matches!(Test::new(2).foo().bar(), Test::<(Y, Y)>::Named { x: 2, .. }));
+    assert_eq!(Test::new(3).foo().bar().get(), Some(3));
+}
@@ -0,0 +1,40 @@
+use stated::{stated, Choice, N, Y};
+
+#[stated(states(A), group(Phase = Idle | Running | Stopped))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(assign(A))]
+    pub fn new_a() -> Test<_> {
+        Test
+    }
+
+    #[stated(assert(Phase = Idle), assign(Phase = Running))]
+    pub fn start(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assert(Phase = Running), assign(Phase = Stopped))]
+    pub fn stop(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assign(Phase = Idle))]
+    pub fn reset(self) -> Test<_> {
+        _
+    }
+}
+
+fn main() {
+    let _: Test<(N, Choice<0>)> = Test::new();
+    let _: Test<(Y, Choice<0>)> = Test::new_a();
+    let _: Test<(N, Choice<1>)> = Test::new().start();
+    let _: Test<(N, Choice<2>)> = Test::new().start().stop();
+    let _: Test<(N, Choice<1>)> = Test::new().start().stop().reset().start();
+}
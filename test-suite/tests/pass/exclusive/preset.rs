@@ -0,0 +1,22 @@
+use stated::{stated, Choice};
+
+#[stated(group(Phase = Idle | Running), preset(Phase = Running))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(assign(Phase = Idle))]
+    pub fn new_idle() -> Test<_> {
+        Test
+    }
+}
+
+fn main() {
+    let _: Test<Choice<1>> = Test::new();
+    let _: Test<Choice<0>> = Test::new_idle();
+}
@@ -0,0 +1,44 @@
+use stated::stated;
+
+#[stated(states(HasEmail, HasPhone, Verified))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(assign(HasEmail))]
+    pub fn email(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assign(HasPhone))]
+    pub fn phone(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assert(any(HasEmail, HasPhone)), assign(Verified))]
+    pub fn verify(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assert(Verified), reject(all(HasEmail, HasPhone)))]
+    pub fn single_contact(&self) -> bool {
+        true
+    }
+
+    #[stated(assert(not(any(HasEmail, HasPhone))))]
+    pub fn anonymous(&self) -> bool {
+        true
+    }
+}
+
+fn main() {
+    assert!(Test::new().anonymous());
+    assert!(Test::new().email().verify().single_contact());
+    assert!(Test::new().phone().verify().single_contact());
+    let _ = Test::new().email().phone().verify();
+}
@@ -0,0 +1,36 @@
+use stated::{stated, N, Y};
+
+#[stated(states(conn::Open, auth::LoggedIn, auth::Admin), preset(auth::LoggedIn))]
+pub struct Test<#[stated(conn)] C, T, #[stated(auth)] A> {
+    x: T,
+}
+
+#[stated]
+impl<#[stated(conn)] C, T, #[stated(auth)] A> Test<C, T, A> {
+    #[stated]
+    pub fn new(x: T) -> Test<_, T, _> {
+        Test { x }
+    }
+
+    #[stated(assign(conn::Open))]
+    pub fn open(self) -> Test<_, T, _> {
+        _
+    }
+
+    #[stated(assert(conn::Open, auth::LoggedIn), assign(auth::Admin))]
+    pub fn elevate(self) -> Test<_, T, _> {
+        _
+    }
+
+    #[stated(assert(auth::Admin))]
+    pub fn get(self) -> T {
+        self.x
+    }
+}
+
+fn main() {
+    let _: Test<N, i32, (Y, N)> = Test::new(1);
+    let _: Test<Y, i32, (Y, N)> = Test::new(1).open();
+    let _: Test<Y, i32, (Y, Y)> = Test::new(1).open().elevate();
+    assert_eq!(Test::new(1).open().elevate().get(), 1);
+}
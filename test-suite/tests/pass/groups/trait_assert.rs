@@ -0,0 +1,28 @@
+use stated::stated;
+
+#[stated(states(conn::Open, auth::LoggedIn))]
+pub struct Test<#[stated(conn)] C, #[stated(auth)] A>;
+
+#[stated]
+impl<#[stated(conn)] C, #[stated(auth)] A> Test<C, A> {
+    #[stated]
+    pub fn new() -> Test<_, _> {
+        Test
+    }
+
+    #[stated(assign(conn::Open))]
+    pub fn open(self) -> Test<_, _> {
+        _
+    }
+}
+
+#[stated(assert(conn::Open))]
+impl<#[stated(conn)] C, #[stated(auth)] A> Clone for Test<C, A> {
+    fn clone(&self) -> Self {
+        Test
+    }
+}
+
+fn main() {
+    let _ = Test::new().open().clone();
+}
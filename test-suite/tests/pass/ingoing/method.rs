@@ -0,0 +1,84 @@
+use stated::stated;
+
+#[stated(states(HasRecipient, HasBody))]
+pub struct MessageBuilder<#[stated] S> {
+    recipients: Vec<String>,
+    body: String,
+}
+
+#[stated]
+impl<#[stated] S> MessageBuilder<S> {
+    #[stated]
+    pub fn new() -> MessageBuilder<_> {
+        MessageBuilder {
+            recipients: Vec::new(),
+            body: String::new(),
+        }
+    }
+
+    #[stated(assign(HasRecipient))]
+    pub fn recipient(mut self, recipient: &str) -> MessageBuilder<_> {
+        self.recipients.push(recipient.to_owned());
+        _
+    }
+
+    #[stated(assign(HasBody))]
+    pub fn body(mut self, body: &str) -> MessageBuilder<_> {
+        self.body = body.to_owned();
+        _
+    }
+
+    #[stated(assert(HasRecipient))]
+    pub fn try_build(self) -> Result<String, MessageBuilder<S>> {
+        if self.body.is_empty() {
+            return Err(self);
+        }
+
+        Ok(format!("{}: {}", self.recipients.join(", "), self.body))
+    }
+
+    #[stated]
+    pub fn merge(mut self, other: MessageBuilder<S>) -> MessageBuilder<S> {
+        let recipients: Vec<String> = other.recipients;
+        self.recipients.extend(recipients);
+        self
+    }
+}
+
+pub trait Reset {
+    type Output;
+
+    fn reset(self) -> Self::Output;
+}
+
+#[stated]
+impl<#[stated] S> Reset for MessageBuilder<S> {
+    type Output = (MessageBuilder<S>, MessageBuilder<_>);
+
+    #[stated(delete(HasBody))]
+    fn reset(self) -> Self::Output {
+        let builder: MessageBuilder<S> = self;
+        let body = MessageBuilder {
+            recipients: builder.recipients.clone(),
+            body: String::new(),
+        };
+
+        (builder, body)
+    }
+}
+
+fn main() {
+    let builder = MessageBuilder::new().recipient("alice");
+    let builder = builder.try_build().unwrap_err();
+
+    let other = MessageBuilder::new().recipient("bob");
+    let builder = builder.body("Hello").merge(other.body("Hi"));
+
+    assert_eq!(
+        builder.try_build().ok().as_deref(),
+        Some("alice, bob: Hello")
+    );
+
+    let (_, reset) = MessageBuilder::new().body("Hello").reset();
+    assert!(reset.body.is_empty());
+}
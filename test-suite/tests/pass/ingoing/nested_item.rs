@@ -0,0 +1,40 @@
+use stated::{stated, Y};
+
+#[stated(states(A))]
+pub struct Test<#[stated] S> {
+    x: u8,
+}
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new(x: u8) -> Test<_> {
+        fn helper<S: Into<u8>>(s: S) -> u8 {
+            s.into()
+        }
+
+        Test { x: helper(x) }
+    }
+
+    #[stated(assign(A))]
+    pub fn a(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assert(A))]
+    pub fn sum(&self) -> u32 {
+        fn helper<S: Into<u32>>(s: S) -> u32 {
+            s.into()
+        }
+
+        struct Wrapper<S>(S);
+
+        let _: Option<Test<S>> = None;
+        helper(Wrapper(self.x).0)
+    }
+}
+
+fn main() {
+    let test: Test<Y> = Test::new(1).a();
+    assert_eq!(test.sum(), 1);
+}
@@ -0,0 +1,33 @@
+use stated::stated;
+
+#[stated(states(Connected { Encrypted }), preset(Encrypted))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(assert(Connected, Encrypted))]
+    pub fn is_encrypted(&self) -> bool {
+        true
+    }
+
+    #[stated(toggle(Connected))]
+    pub fn toggle(self) -> Test<_> {
+        _
+    }
+
+    #[stated(reject(Encrypted))]
+    pub fn is_plain(&self) -> bool {
+        true
+    }
+}
+
+fn main() {
+    assert!(Test::new().is_encrypted());
+    assert!(Test::new().toggle().is_plain());
+    assert!(Test::new().toggle().toggle().is_plain());
+}
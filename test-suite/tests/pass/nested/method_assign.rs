@@ -0,0 +1,55 @@
+use stated::stated;
+
+#[stated(states(Connected { Authenticated { Admin }, Encrypted }, Closed))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(assign(Connected))]
+    pub fn connect(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assign(Authenticated))]
+    pub fn authenticate(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assign(Admin))]
+    pub fn elevate(self) -> Test<_> {
+        _
+    }
+
+    #[stated(delete(Connected), assign(Closed))]
+    pub fn close(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assert(Admin))]
+    pub fn is_connected_admin(&self) -> bool {
+        true
+    }
+
+    #[stated(assert(Connected, Authenticated, Admin))]
+    pub fn is_connected_as_admin(&self) -> bool {
+        true
+    }
+
+    #[stated(assert(Closed), reject(Connected))]
+    pub fn is_closed(&self) -> bool {
+        true
+    }
+}
+
+fn main() {
+    let test = Test::new().elevate();
+    assert!(test.is_connected_admin());
+    assert!(test.is_connected_as_admin());
+    assert!(test.close().is_closed());
+    let _ = Test::new().connect().authenticate();
+}
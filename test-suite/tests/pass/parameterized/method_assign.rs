@@ -0,0 +1,78 @@
+use stated::stated;
+
+pub trait Codec {
+    const NAME: &'static str;
+}
+
+pub struct Gzip;
+
+impl Codec for Gzip {
+    const NAME: &'static str = "gzip";
+}
+
+pub struct Zstd;
+
+impl Codec for Zstd {
+    const NAME: &'static str = "zstd";
+}
+
+#[stated(states(Encoding<E>, Buffered), alias(Ready = Encoding + Buffered))]
+pub struct Serializer<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Serializer<S> {
+    #[stated]
+    pub fn new() -> Serializer<_> {
+        Serializer
+    }
+
+    #[stated(assign(Encoding<Zstd>))]
+    pub fn zstd() -> Serializer<_> {
+        Serializer
+    }
+
+    #[stated(assign(Encoding<Gzip>))]
+    pub fn gzip(self) -> Serializer<_> {
+        _
+    }
+
+    #[stated(assign(Buffered))]
+    pub fn buffered(self) -> Serializer<_> {
+        _
+    }
+
+    #[stated(delete(Encoding))]
+    pub fn raw(self) -> Serializer<_> {
+        _
+    }
+
+    #[stated(assert(Encoding<_>))]
+    pub fn build(self) -> &'static str
+    where
+        E: Codec,
+    {
+        E::NAME
+    }
+
+    #[stated(assert(Encoding<Gzip>))]
+    pub fn is_gzip(&self) -> bool {
+        true
+    }
+
+    #[stated(reject(Encoding))]
+    pub fn is_raw(&self) -> bool {
+        true
+    }
+}
+
+fn ready<E: Codec>(serializer: SerializerReady<E>) -> &'static str {
+    serializer.build()
+}
+
+fn main() {
+    assert!(Serializer::new().is_raw());
+    assert!(Serializer::new().gzip().is_gzip());
+    assert_eq!(Serializer::new().gzip().build(), "gzip");
+    assert_eq!(ready(Serializer::zstd().buffered()), "zstd");
+    assert!(Serializer::zstd().raw().is_raw());
+}
@@ -0,0 +1,32 @@
+use stated::stated;
+
+#[stated(phases(Created, Connected, Closed), preset(Connected))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(advance(Closed))]
+    pub fn closed() -> Test<_> {
+        Test
+    }
+
+    #[stated(assert(Connected))]
+    pub fn is_connected(&self) -> bool {
+        true
+    }
+
+    #[stated(assert(at_least(Closed)))]
+    pub fn is_closed(&self) -> bool {
+        true
+    }
+}
+
+fn main() {
+    assert!(Test::new().is_connected());
+    assert!(Test::closed().is_closed());
+}
@@ -0,0 +1,53 @@
+use stated::stated;
+
+#[stated(phases(Created, Configured, Connected, Closed), states(Logged))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(advance(Configured))]
+    pub fn configure(self) -> Test<_> {
+        _
+    }
+
+    #[stated(advance(Connected))]
+    pub fn connect(self) -> Test<_> {
+        _
+    }
+
+    #[stated(advance(Closed))]
+    pub fn close(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assign(Logged))]
+    pub fn log(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assert(at_least(Configured)))]
+    pub fn is_configured(&self) -> bool {
+        true
+    }
+
+    #[stated(assert(Connected))]
+    pub fn is_connected(&self) -> bool {
+        true
+    }
+}
+
+fn main() {
+    let test = Test::new().configure();
+    assert!(test.is_configured());
+
+    let test = test.log().connect();
+    assert!(test.is_configured());
+    assert!(test.is_connected());
+
+    assert!(Test::new().connect().close().is_configured());
+}
@@ -0,0 +1,80 @@
+use std::{rc::Rc, sync::Arc};
+
+use stated::{stated, N, Y};
+
+#[stated(states(A, B))]
+pub struct Test<#[stated] S> {
+    data: [u8; 256],
+}
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Box<Test<_>> {
+        Box::new(Test { data: [1; 256] })
+    }
+
+    #[stated(assign(A))]
+    pub fn a(self: Box<Self>) -> Box<Test<_>> {
+        _
+    }
+
+    #[stated(assert(A), assign(B))]
+    pub fn b(self: Box<Test<S>>) -> Box<Test<_>> {
+        _
+    }
+
+    #[stated(assign(A))]
+    pub fn rc_a(self: Rc<Self>) -> Result<Rc<Test<_>>, Rc<Self>> {
+        _
+    }
+
+    #[stated(assign(B))]
+    pub fn arc_b(self: Arc<Self>) -> Result<Arc<Test<_>>, Arc<Self>> {
+        _
+    }
+
+    #[stated(borrow)]
+    pub fn sum(&self) -> u32 {
+        self.data.iter().map(|&byte| u32::from(byte)).sum()
+    }
+}
+
+#[stated(states(Open))]
+pub enum Connection<#[stated] S> {
+    Tcp(u16),
+}
+
+#[stated]
+impl<#[stated] S> Connection<S> {
+    #[stated]
+    pub fn new(port: u16) -> Box<Connection<_>> {
+        Box::new(Connection::Tcp(port))
+    }
+
+    #[stated(assign(Open))]
+    pub fn open(self: Box<Self>) -> Box<Connection<_>> {
+        _
+    }
+}
+
+fn main() {
+    let test = Test::new();
+    let address = &*test as *const _ as usize;
+
+    let test: Box<Test<(Y, Y)>> = test.a().b();
+    assert_eq!(&*test as *const _ as usize, address);
+    assert_eq!(test.sum(), 256);
+
+    let test = Rc::new(*Test::new());
+    let shared = Rc::clone(&test);
+    let test = test.rc_a().err().unwrap();
+    drop(shared);
+    let _: Rc<Test<(Y, N)>> = test.rc_a().ok().unwrap();
+
+    let test = Arc::new(*Test::new());
+    let _: Arc<Test<(N, Y)>> = test.arc_b().ok().unwrap();
+
+    let Connection::Tcp(port, ..) = *Connection::new(80).open();
+    assert_eq!(port, 80);
+}
@@ -0,0 +1,86 @@
+use std::mem::{align_of, offset_of, size_of};
+
+use stated::{stated, Choice, Succ, With, Zero, N, Y};
+
+#[stated(
+    states(A, Encoding<E>, HasBody),
+    group(Mode = Fast | Slow),
+    counter(Retries, max = 2),
+    repr_transparent_states
+)]
+pub struct Test<#[stated] S> {
+    data: [u64; 64],
+    flag: u8,
+    #[stated(when(HasBody) = String)]
+    body: (),
+}
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test {
+            data: [7; 64],
+            flag: 1,
+            body: (),
+        }
+    }
+
+    #[stated(assign(A))]
+    pub fn a(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assign(Encoding<u32>))]
+    pub fn encode(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assign(Mode = Slow), increment(Retries))]
+    pub fn slow(self) -> Test<_> {
+        _
+    }
+
+    #[stated(assign(A))]
+    pub fn boxed(self: Box<Self>) -> Box<Test<_>> {
+        _
+    }
+
+    #[stated(borrow)]
+    pub fn sum(&self) -> u64 {
+        self.data.iter().sum::<u64>() + u64::from(self.flag)
+    }
+}
+
+macro_rules! assert_same_layout {
+    ($first:ty, $($other:ty),* $(,)?) => {
+        $(
+            assert_eq!(size_of::<$first>(), size_of::<$other>());
+            assert_eq!(align_of::<$first>(), align_of::<$other>());
+            assert_eq!(offset_of!($first, data), offset_of!($other, data));
+            assert_eq!(offset_of!($first, flag), offset_of!($other, flag));
+            assert_eq!(offset_of!($first, body), offset_of!($other, body));
+        )*
+    };
+}
+
+fn main() {
+    assert_same_layout!(
+        Test<(N, N, N, Choice<0>, (Zero, Succ<Succ<Zero>>))>,
+        Test<(Y, N, N, Choice<0>, (Zero, Succ<Succ<Zero>>))>,
+        Test<(N, With<u32>, N, Choice<0>, (Zero, Succ<Succ<Zero>>))>,
+        Test<(Y, With<String>, N, Choice<1>, (Succ<Zero>, Succ<Zero>))>,
+        Test<(Y, With<[u8; 1024]>, N, Choice<1>, (Succ<Succ<Zero>>, Zero))>,
+    );
+
+    assert_same_layout!(
+        Test<(N, N, Y, Choice<0>, (Zero, Succ<Succ<Zero>>))>,
+        Test<(Y, With<u32>, Y, Choice<1>, (Succ<Zero>, Succ<Zero>))>,
+    );
+
+    let test = Test::new().a().encode().slow();
+    assert_eq!(test.sum(), 7 * 64 + 1);
+
+    let test = Box::new(Test::new().encode()).boxed();
+    assert_eq!(test.sum(), 7 * 64 + 1);
+}
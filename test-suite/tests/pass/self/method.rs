@@ -0,0 +1,111 @@
+use stated::{stated, N, Y};
+
+#[stated(states(A, B))]
+pub struct Test<T, #[stated] S> {
+    x: T,
+}
+
+#[stated]
+impl<T, #[stated] S> Test<T, S> {
+    #[stated]
+    pub fn new(x: T) -> Self {
+        Self { x }
+    }
+
+    #[stated(assign(B))]
+    pub fn with_b(x: T) -> Self<T, _> {
+        let test = Self { x };
+        test
+    }
+
+    #[stated(assign(A))]
+    pub fn a(self) -> Self<T, _> {
+        _
+    }
+
+    #[stated(assert(A), assign(B))]
+    pub fn b(self) -> Result<Self<T, _>, Self> {
+        let Self { x, .. } = &self;
+        let _: &T = x;
+        Ok(_)
+    }
+
+    #[stated(reject(B))]
+    pub fn replace(self, x: T) -> Self {
+        Self { x }
+    }
+
+    #[stated]
+    pub fn into_inner(self) -> T {
+        let Self { x, .. } = self;
+        x
+    }
+}
+
+#[stated(states(Open))]
+pub struct Unit<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Unit<S> {
+    #[stated]
+    pub fn new() -> Self {
+        Self
+    }
+
+    #[stated(assign(Open))]
+    pub fn open(self) -> Self<_> {
+        let Self = self;
+        Self
+    }
+}
+
+#[stated(states(Open))]
+pub enum Connection<#[stated] S> {
+    Tcp(u16),
+    Closed,
+}
+
+#[stated]
+impl<#[stated] S> Connection<S> {
+    #[stated]
+    pub fn new(port: u16) -> Self {
+        Self::Tcp(port)
+    }
+
+    #[stated(assign(Open))]
+    pub fn open(self) -> Self<_> {
+        match self {
+            Self::Tcp(port) => Self::Tcp(port),
+            Self::Closed => Self::Closed,
+        }
+    }
+}
+
+#[stated]
+impl<#[stated] S> Default for Connection<S> {
+    #[stated]
+    fn default() -> Self {
+        Self::Closed
+    }
+}
+
+fn main() {
+    let Ok(test) = Test::new(1).a().b() else {
+        unreachable!();
+    };
+    let _: Test<i32, (Y, Y)> = test;
+
+    let test = Test::with_b(2);
+    let _: Test<i32, (N, Y)> = test;
+
+    let test = Test::new(3).replace(4);
+    let _: Test<i32, (N, N)> = test;
+    assert_eq!(test.into_inner(), 4);
+
+    let _: Unit<Y> = Unit::new().open();
+
+    let Connection::Tcp(port, ..) = Connection::new(80).open() else {
+        unreachable!();
+    };
+    assert_eq!(port, 80);
+}
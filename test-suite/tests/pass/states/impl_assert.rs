@@ -0,0 +1,35 @@
+use stated::{stated, N, Y};
+
+#[stated(states(A, B, C))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(assign(A))]
+    pub fn foo(self) -> Test<_> {
+        _
+    }
+}
+
+#[stated(assert(A))]
+impl<#[stated] S> Test<S> {
+    #[stated(assign(B))]
+    pub fn bar(self) -> Test<_> {
+        _
+    }
+
+    #[stated(reject(B), assign(C))]
+    pub fn baz(self) -> Test<_> {
+        _
+    }
+}
+
+fn main() {
+    assert!(matches!(Test::new().foo().bar(), Test::<(Y, Y, N)> { .. }));
+    assert!(matches!(Test::new().foo().baz(), Test::<(Y, N, Y)> { .. }));
+}
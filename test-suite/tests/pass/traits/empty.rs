@@ -0,0 +1,23 @@
+use stated::stated;
+
+pub trait Marker {}
+
+#[stated(states(A))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+}
+
+#[stated]
+impl<#[stated] S> Marker for Test<S> {}
+
+fn is_marker(_: impl Marker) {}
+
+fn main() {
+    is_marker(Test::new());
+}
@@ -0,0 +1,30 @@
+use std::fmt::{self, Display, Formatter};
+
+use stated::stated;
+
+#[stated(states(A, B))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+
+    #[stated(assign(A))]
+    pub fn foo(self) -> Test<_> {
+        _
+    }
+}
+
+#[stated(assert(A), reject(B))]
+impl<#[stated] S> Display for Test<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Hello, World!")
+    }
+}
+
+fn main() {
+    assert_eq!(Test::new().foo().to_string(), "Hello, World!");
+}
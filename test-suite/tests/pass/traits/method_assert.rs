@@ -0,0 +1,34 @@
+use stated::stated;
+
+#[stated(states(A, B))]
+pub struct Test<#[stated] S> {
+    count: u32,
+}
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test { count: 0 }
+    }
+
+    #[stated(assign(A))]
+    pub fn foo(self) -> Test<_> {
+        _
+    }
+}
+
+#[stated]
+impl<#[stated] S> Iterator for Test<S> {
+    type Item = u32;
+
+    #[stated(assert(A))]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.count += 1;
+        (self.count <= 3).then_some(self.count)
+    }
+}
+
+fn main() {
+    assert_eq!(Test::new().foo().sum::<u32>(), 6);
+}
@@ -0,0 +1,32 @@
+use stated::{stated, N, Y};
+
+pub trait Finish {
+    type Output;
+
+    fn finish(self) -> Self::Output;
+}
+
+#[stated(states(A, B))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+}
+
+#[stated]
+impl<#[stated] S> Finish for Test<S> {
+    type Output = Test<_>;
+
+    #[stated(reject(A), assign(B))]
+    fn finish(self) -> Test<_> {
+        _
+    }
+}
+
+fn main() {
+    assert!(matches!(Test::new().finish(), Test::<(N, Y)> { .. }));
+}
@@ -0,0 +1,41 @@
+use stated::{stated, N, Y};
+
+pub trait Finish {
+    type Output;
+
+    fn finish(self) -> Self::Output;
+
+    fn is_finished(&self) -> bool;
+}
+
+#[stated(states(A, B))]
+pub struct Test<#[stated] S>;
+
+#[stated]
+impl<#[stated] S> Test<S> {
+    #[stated]
+    pub fn new() -> Test<_> {
+        Test
+    }
+}
+
+#[stated]
+impl<#[stated] S> Finish for Test<S> {
+    type Output = Test<_>;
+
+    #[stated(assign(B))]
+    fn finish(self) -> Test<_> {
+        _
+    }
+
+    #[stated(reject(A))]
+    fn is_finished(&self) -> bool {
+        false
+    }
+}
+
+fn main() {
+    let test = Test::new();
+    assert!(!test.is_finished());
+    assert!(matches!(test.finish(), Test::<(N, Y)> { .. }));
+}